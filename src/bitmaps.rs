@@ -5,3 +5,34 @@ pub const CHECK_GLYPH: Glyph = Glyph::from_include(include_gif!("icons/check.gif
 pub const BACK_GLYPH: Glyph = Glyph::from_include(include_gif!("icons/back.gif"));
 pub const DASHBOARD_GLYPH: Glyph = Glyph::from_include(include_gif!("icons/dashboard.gif"));
 pub const SETTINGS_GLYPH: Glyph = Glyph::from_include(include_gif!("icons/settings.gif"));
+pub const DOWN_GLYPH: Glyph = Glyph::from_include(include_gif!("icons/down.gif"));
+pub const DOT_GLYPH: Glyph = Glyph::from_include(include_gif!("icons/dot.gif"));
+pub const WARNING_GLYPH: Glyph = Glyph::from_include(include_gif!("icons/warning.gif"));
+pub const WARNING_SMALL_GLYPH: Glyph = Glyph::from_include(include_gif!("icons/warning_small.gif"));
+
+/// Glyphs are packed into bitmaps at compile time by `include_gif!`, so nothing is decoded
+/// lazily and there is nothing to preload; this does nothing. It exists so that apps can warm
+/// up the UI at startup without depending on how glyphs are stored.
+#[inline(always)]
+pub fn preload_glyphs() {}
+
+/// Small 3x5 digits, indexed by value, for compact counters.
+pub const SMALL_DIGIT_GLYPHS: [Glyph; 10] = [
+    Glyph::from_include(include_gif!("icons/digit_0.gif")),
+    Glyph::from_include(include_gif!("icons/digit_1.gif")),
+    Glyph::from_include(include_gif!("icons/digit_2.gif")),
+    Glyph::from_include(include_gif!("icons/digit_3.gif")),
+    Glyph::from_include(include_gif!("icons/digit_4.gif")),
+    Glyph::from_include(include_gif!("icons/digit_5.gif")),
+    Glyph::from_include(include_gif!("icons/digit_6.gif")),
+    Glyph::from_include(include_gif!("icons/digit_7.gif")),
+    Glyph::from_include(include_gif!("icons/digit_8.gif")),
+    Glyph::from_include(include_gif!("icons/digit_9.gif")),
+];
+pub const SMALL_SLASH_GLYPH: Glyph = Glyph::from_include(include_gif!("icons/digit_slash.gif"));
+pub const SMALL_DIGIT_WIDTH: i16 = 3;
+
+/// 3x3 dots marking the current page and the other pages in a page counter drawn as dots.
+pub const PAGE_DOT_GLYPH: Glyph = Glyph::from_include(include_gif!("icons/page_dot.gif"));
+pub const PAGE_DOT_EMPTY_GLYPH: Glyph =
+    Glyph::from_include(include_gif!("icons/page_dot_empty.gif"));
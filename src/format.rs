@@ -0,0 +1,478 @@
+//! Formatters that write values into a `core::fmt::Write`, typically the `PromptWrite` handed
+//! to a scroller's content closure.
+
+use core::fmt::{Result, Write};
+
+/// Write `amount` base units as a decimal with `decimals` fractional digits, trailing zeros
+/// trimmed, followed by `symbol` if it is not empty, e.g. "1.5 XTZ".
+pub fn write_amount<W: Write>(w: &mut W, amount: u128, decimals: u8, symbol: &str) -> Result {
+    let divisor = 10u128
+        .checked_pow(decimals as u32)
+        .ok_or(core::fmt::Error)?;
+    write!(w, "{}", amount / divisor)?;
+    let mut frac = amount % divisor;
+    if frac != 0 {
+        let mut digits = decimals as usize;
+        while frac % 10 == 0 {
+            frac /= 10;
+            digits -= 1;
+        }
+        write!(w, ".{:0width$}", frac, width = digits)?;
+    }
+    if !symbol.is_empty() {
+        write!(w, " {}", symbol)?;
+    }
+    Ok(())
+}
+
+/// Write `bytes` as lowercase hex. With `reverse`, the last byte is written first, e.g. to show
+/// a little-endian value in numeric order.
+pub fn write_hex<W: Write>(w: &mut W, bytes: &[u8], reverse: bool) -> Result {
+    write_hex_dump(
+        w,
+        bytes,
+        HexDump {
+            reverse,
+            ..HexDump::default()
+        },
+    )
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// How `write_hex_dump` writes bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HexDump {
+    pub uppercase: bool,
+    /// Write a space after every `group` bytes but the last ones; 0 writes no spaces.
+    pub group: usize,
+    /// Write the last byte first, as `write_hex` does, e.g. for a little-endian value.
+    pub reverse: bool,
+}
+
+/// Write `bytes` as hex in the style of `dump`, e.g. "DEADBEEF 0102" with uppercase digits in
+/// groups of 4. Shown with `WriteScroller::with_word_wrap`, groups are not split between pages.
+pub fn write_hex_dump<W: Write>(w: &mut W, bytes: &[u8], dump: HexDump) -> Result {
+    for i in 0..bytes.len() {
+        let b = if dump.reverse {
+            bytes[bytes.len() - 1 - i]
+        } else {
+            bytes[i]
+        };
+        if dump.group > 0 && i > 0 && i % dump.group == 0 {
+            w.write_char(' ')?;
+        }
+        if dump.uppercase {
+            write!(w, "{:02X}", b)?;
+        } else {
+            write!(w, "{:02x}", b)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write `bytes` in standard, padded base64. The encoding is streamed three bytes at a time, so
+/// no buffer for the whole output is needed.
+pub fn write_base64<W: Write>(w: &mut W, bytes: &[u8]) -> Result {
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as usize) << 16 | (b[1] as usize) << 8 | b[2] as usize;
+        let mut out = [b'='; 4];
+        for (i, c) in out.iter_mut().enumerate().take(chunk.len() + 1) {
+            *c = BASE64_ALPHABET[(n >> (18 - 6 * i)) & 0x3f];
+        }
+        // Only ASCII has been written to `out`
+        w.write_str(core::str::from_utf8(&out).map_err(|_| core::fmt::Error)?)?;
+    }
+    Ok(())
+}
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Most bytes `write_base58` encodes.
+pub const MAX_BASE58_INPUT: usize = 128;
+
+/// Write `bytes` in plain base58 with the Bitcoin alphabet, each leading zero byte as a `1`.
+/// The digits are worked out in a buffer on the stack sized for `MAX_BASE58_INPUT` bytes, and
+/// longer input is an error. See `write_base58_check` for addresses with a checksum.
+pub fn write_base58<W: Write>(w: &mut W, bytes: &[u8]) -> Result {
+    if bytes.len() > MAX_BASE58_INPUT {
+        return Err(core::fmt::Error);
+    }
+    let zeros = bytes.iter().take_while(|b| **b == 0).count();
+    // Digits of the value of the rest, least significant first; log(256) / log(58) < 1.37
+    let mut digits = [0u8; MAX_BASE58_INPUT * 137 / 100 + 1];
+    let mut len = 0;
+    for b in &bytes[zeros..] {
+        let mut carry = *b as u32;
+        for digit in digits[..len].iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits[len] = (carry % 58) as u8;
+            len += 1;
+            carry /= 58;
+        }
+    }
+    for _ in 0..zeros {
+        w.write_char('1')?;
+    }
+    for digit in digits[..len].iter().rev() {
+        w.write_char(BASE58_ALPHABET[*digit as usize] as char)?;
+    }
+    Ok(())
+}
+
+/// Length of the checksum `write_base58_check` appends.
+pub const BASE58_CHECKSUM_LEN: usize = 4;
+
+/// Write `payload` in Base58Check, as `write_base58` with the first bytes of the double
+/// SHA-256 of `payload` appended. The hash is left to the caller, e.g. to the SDK on a device,
+/// as `double_sha256`. Payloads longer than `MAX_BASE58_INPUT` less the checksum are an error.
+pub fn write_base58_check<W: Write>(
+    w: &mut W,
+    payload: &[u8],
+    double_sha256: impl FnOnce(&[u8]) -> [u8; 32],
+) -> Result {
+    let len = payload.len() + BASE58_CHECKSUM_LEN;
+    if len > MAX_BASE58_INPUT {
+        return Err(core::fmt::Error);
+    }
+    let mut bytes = [0u8; MAX_BASE58_INPUT];
+    bytes[..payload.len()].copy_from_slice(payload);
+    bytes[payload.len()..len].copy_from_slice(&double_sha256(payload)[..BASE58_CHECKSUM_LEN]);
+    write_base58(w, &bytes[..len])
+}
+
+/// Bit set in the components of a BIP32 path that use hardened derivation.
+pub const HARDENED: u32 = 0x8000_0000;
+
+/// Write a BIP32 derivation path, e.g. "m/44'/1729'/0'/0'", with hardened components marked
+/// by `'`.
+pub fn write_bip32<W: Write>(w: &mut W, path: &[u32]) -> Result {
+    w.write_str("m")?;
+    for component in path {
+        write!(w, "/{}", component & !HARDENED)?;
+        if component & HARDENED != 0 {
+            w.write_str("'")?;
+        }
+    }
+    Ok(())
+}
+
+/// Write a label for the account that `path` derives, e.g. "Account #3", if it follows one of
+/// the BIP44-style layouts starting with purpose'/coin_type'/account'. Other paths are written
+/// in full with `write_bip32`.
+pub fn write_account_label<W: Write>(w: &mut W, path: &[u32]) -> Result {
+    match path {
+        [purpose, coin_type, account, ..]
+            if [44, 49, 84, 86].contains(&(purpose & !HARDENED))
+                && purpose & coin_type & account & HARDENED != 0 =>
+        {
+            write!(
+                w,
+                "{} #{}",
+                crate::strings::strings().account,
+                account & !HARDENED
+            )
+        }
+        _ => write_bip32(w, path),
+    }
+}
+
+/// Marks the part of a text left out by `write_truncated`. The device font has no "…".
+pub const ELLIPSIS: &str = "...";
+
+/// Which part of a text `write_truncated` keeps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TruncateMode {
+    /// Keep the start and the end, e.g. "tz1VS...jcjb".
+    Middle,
+    /// Keep the start, e.g. "tz1VSUr8w...".
+    Head,
+    /// Keep the end, e.g. "...iTh8Cjcjb".
+    Tail,
+}
+
+/// Write `text`, or if it is longer than `max_chars` characters, as much of it as fits in
+/// `max_chars` together with `ELLIPSIS` in the place given by `mode`. Characters are counted
+/// and cut as chars, never within one.
+pub fn write_truncated<W: Write>(
+    w: &mut W,
+    text: &str,
+    max_chars: usize,
+    mode: TruncateMode,
+) -> Result {
+    write_truncated_with(w, text, max_chars, mode, ELLIPSIS)
+}
+
+/// Like `write_truncated`, but marking the left out part with `ellipsis`, e.g. ".." to keep
+/// more of a hash, or "…" for a screen with a font that has it. If `max_chars` is too few for
+/// all of `ellipsis`, only its first `max_chars` chars are written.
+pub fn write_truncated_with<W: Write>(
+    w: &mut W,
+    text: &str,
+    max_chars: usize,
+    mode: TruncateMode,
+    ellipsis: &str,
+) -> Result {
+    let len = text.chars().count();
+    if len <= max_chars {
+        return w.write_str(text);
+    }
+    let ellipsis = &ellipsis[..char_boundary(ellipsis, max_chars)];
+    let keep = max_chars - ellipsis.chars().count();
+    let (head, tail) = match mode {
+        TruncateMode::Middle => (keep - keep / 2, keep / 2),
+        TruncateMode::Head => (keep, 0),
+        TruncateMode::Tail => (0, keep),
+    };
+    w.write_str(&text[..char_boundary(text, head)])?;
+    w.write_str(ellipsis)?;
+    w.write_str(&text[char_boundary(text, len - tail)..])
+}
+
+/// Byte offset of the char at index `n` of `text`, or its length if there are fewer chars.
+fn char_boundary(text: &str, n: usize) -> usize {
+    text.char_indices().nth(n).map_or(text.len(), |(i, _)| i)
+}
+
+/// Deepest nesting that `write_indented` indents for; deeper items are indented as this.
+pub const MAX_INDENT_DEPTH: usize = 4;
+
+/// Write `key: value` indented by two spaces per level of `depth`, up to `MAX_INDENT_DEPTH`
+/// levels so that deep nesting still leaves room for the item itself.
+pub fn write_indented<W: Write>(w: &mut W, depth: usize, key: &str, value: &str) -> Result {
+    for _ in 0..core::cmp::min(depth, MAX_INDENT_DEPTH) {
+        w.write_str("  ")?;
+    }
+    write!(w, "{}: {}", key, value)
+}
+
+/// Write `timestamp`, in seconds since the Unix epoch, as a UTC date and time, e.g.
+/// "2024-05-01 12:00:00 UTC".
+pub fn write_timestamp<W: Write>(w: &mut W, timestamp: i64) -> Result {
+    let days = timestamp.div_euclid(86400);
+    let seconds = timestamp.rem_euclid(86400);
+    // Civil date from days since the epoch, counting in 400-year eras starting in March
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    write!(
+        w,
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::String;
+
+    fn written(write: impl FnOnce(&mut String) -> Result) -> String {
+        let mut out = String::new();
+        write(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn amounts() {
+        assert_eq!(written(|w| write_amount(w, 1_500_000, 6, "XTZ")), "1.5 XTZ");
+        assert_eq!(written(|w| write_amount(w, 1, 6, "")), "0.000001");
+        assert_eq!(written(|w| write_amount(w, 100, 2, "")), "1");
+        assert_eq!(written(|w| write_amount(w, 0, 8, "BTC")), "0 BTC");
+        assert_eq!(written(|w| write_amount(w, 12_345, 0, "")), "12345");
+        assert!(write_amount(&mut String::new(), 1, 39, "").is_err());
+    }
+
+    #[test]
+    fn hex() {
+        let bytes = [0xde, 0xad, 0x01];
+        assert_eq!(written(|w| write_hex(w, &bytes, false)), "dead01");
+        assert_eq!(written(|w| write_hex(w, &bytes, true)), "01adde");
+        assert_eq!(written(|w| write_hex(w, &[], false)), "");
+    }
+
+    #[test]
+    fn hex_dumps() {
+        let bytes = [0xde, 0xad, 0xbe, 0xef, 0x01];
+        let dump = HexDump {
+            uppercase: true,
+            group: 2,
+            reverse: false,
+        };
+        assert_eq!(written(|w| write_hex_dump(w, &bytes, dump)), "DEAD BEEF 01");
+        let dump = HexDump::default();
+        assert_eq!(written(|w| write_hex_dump(w, &bytes, dump)), "deadbeef01");
+        let dump = HexDump {
+            group: 2,
+            reverse: true,
+            ..HexDump::default()
+        };
+        assert_eq!(written(|w| write_hex_dump(w, &bytes, dump)), "01ef bead de");
+    }
+
+    fn from_hex(hex: &str) -> std::vec::Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn base58() {
+        // From the test vectors of Bitcoin Core
+        let cases = [
+            ("", ""),
+            ("61", "2g"),
+            ("626262", "a3gV"),
+            ("636363", "aPEr"),
+            (
+                "73696d706c792061206c6f6e6720737472696e67",
+                "2cFupjhnEsSn59qHXstmK2ffpLv2",
+            ),
+            (
+                "00eb15231dfceb60925886b67d065299925915aeb172c06647",
+                "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L",
+            ),
+            ("516b6fcd0f", "ABnLTmg"),
+            ("bf4f89001e670274dd", "3SEo3LWLoPntC"),
+            ("572e4794", "3EFU7m"),
+            ("ecac89cad93923c02321", "EJDM8drfXA6uyA"),
+            ("10c8511e", "Rt5zm"),
+            ("00000000000000000000", "1111111111"),
+        ];
+        for (hex, expected) in cases {
+            assert_eq!(written(|w| write_base58(w, &from_hex(hex))), expected);
+        }
+    }
+
+    #[test]
+    fn base58_up_to_the_largest_input() {
+        let encoded = written(|w| write_base58(w, &[0xff; MAX_BASE58_INPUT]));
+        assert_eq!(encoded.len(), 175);
+        assert!(encoded.starts_with("TCQK6EStJf"));
+        assert!(write_base58(&mut String::new(), &[0xff; MAX_BASE58_INPUT + 1]).is_err());
+    }
+
+    #[test]
+    fn base58_check_appends_the_start_of_the_hash() {
+        let payload = from_hex("00eb15231dfceb60925886b67d065299925915aeb1");
+        let encoded = written(|w| {
+            write_base58_check(w, &payload, |hashed| {
+                assert_eq!(hashed, &payload[..]);
+                let mut hash = [0xaa; 32];
+                hash[..4].copy_from_slice(&[0x72, 0xc0, 0x66, 0x47]);
+                hash
+            })
+        });
+        assert_eq!(encoded, "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L");
+        let too_long = [0; MAX_BASE58_INPUT - BASE58_CHECKSUM_LEN + 1];
+        assert!(write_base58_check(&mut String::new(), &too_long, |_| [0; 32]).is_err());
+    }
+
+    #[test]
+    fn bip32_paths() {
+        let path = [44 | HARDENED, 1729 | HARDENED, HARDENED, HARDENED];
+        assert_eq!(written(|w| write_bip32(w, &path)), "m/44'/1729'/0'/0'");
+        assert_eq!(
+            written(|w| write_bip32(w, &[44 | HARDENED, 0, 5])),
+            "m/44'/0/5"
+        );
+        assert_eq!(written(|w| write_bip32(w, &[])), "m");
+    }
+
+    #[test]
+    fn account_labels() {
+        let path = [84 | HARDENED, HARDENED, 3 | HARDENED, 0, 1];
+        assert_eq!(written(|w| write_account_label(w, &path)), "Account #3");
+        // An account that is not hardened is not taken for one
+        let path = [44 | HARDENED, HARDENED, 3];
+        assert_eq!(written(|w| write_account_label(w, &path)), "m/44'/0'/3");
+    }
+
+    #[test]
+    fn timestamps() {
+        let cases = [
+            (0, "1970-01-01 00:00:00 UTC"),
+            (-1, "1969-12-31 23:59:59 UTC"),
+            (951_782_400, "2000-02-29 00:00:00 UTC"),
+            (1_714_564_800, "2024-05-01 12:00:00 UTC"),
+            (253_402_300_799, "9999-12-31 23:59:59 UTC"),
+        ];
+        for (timestamp, expected) in cases {
+            assert_eq!(written(|w| write_timestamp(w, timestamp)), expected);
+        }
+    }
+
+    #[test]
+    fn base64() {
+        // The test vectors of RFC 4648
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (input, output) in vectors {
+            assert_eq!(written(|w| write_base64(w, input.as_bytes())), output);
+        }
+        assert_eq!(written(|w| write_base64(w, &[0xfb, 0xff])), "+/8=");
+    }
+
+    #[test]
+    fn truncated() {
+        let text = "Détails du dépôt";
+        let truncate = |max_chars, mode| written(|w| write_truncated(w, text, max_chars, mode));
+        assert_eq!(truncate(16, TruncateMode::Middle), text);
+        assert_eq!(truncate(10, TruncateMode::Middle), "Déta...pôt");
+        assert_eq!(truncate(10, TruncateMode::Head), "Détails...");
+        assert_eq!(truncate(10, TruncateMode::Tail), "...u dépôt");
+        assert_eq!(truncate(2, TruncateMode::Middle), "..");
+        assert_eq!(truncate(0, TruncateMode::Tail), "");
+        let truncate = |max_chars| {
+            written(|w| write_truncated_with(w, text, max_chars, TruncateMode::Head, "…"))
+        };
+        assert_eq!(truncate(6), "Détai…");
+        assert_eq!(truncate(1), "…");
+    }
+
+    #[test]
+    fn indented() {
+        let indented = |depth| written(|w| write_indented(w, depth, "key", "value"));
+        assert_eq!(indented(0), "key: value");
+        assert_eq!(indented(1), "  key: value");
+        assert_eq!(indented(2), "    key: value");
+        assert_eq!(indented(MAX_INDENT_DEPTH), "        key: value");
+        assert_eq!(indented(MAX_INDENT_DEPTH + 5), indented(MAX_INDENT_DEPTH));
+    }
+}
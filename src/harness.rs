@@ -0,0 +1,1358 @@
+//! An in-memory stand-in for the screen and the buttons, so that prompts can be driven by host
+//! tests. With the `test-harness` feature, the text that this crate draws is recorded here
+//! instead of being drawn, and button events are taken from a script set with `script`.
+//!
+//! Text is recorded by the row it is drawn at, and icons and rectangles by where they are drawn.
+//! The SDK's `MessageValidator` is stood in for too, and text is measured by a fixed width per
+//! char rather than by the SDK's fonts.
+
+extern crate std;
+
+use crate::*;
+use std::boxed::Box;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// A row of text drawn on the screen, by where it starts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Row {
+    pub x: usize,
+    pub y: usize,
+    pub text: String,
+    pub bold: bool,
+}
+
+/// An icon drawn on the screen, by its top left corner, its size and its bitmap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IconDraw {
+    pub x: i16,
+    pub y: i16,
+    pub width: u32,
+    pub height: u32,
+    pub bitmap: Vec<u8>,
+}
+
+impl From<&Icon<'_>> for IconDraw {
+    fn from(icon: &Icon<'_>) -> IconDraw {
+        IconDraw {
+            x: icon.pos.0,
+            y: icon.pos.1,
+            width: icon.icon.width,
+            height: icon.icon.height,
+            bitmap: icon.icon.bitmap.to_vec(),
+        }
+    }
+}
+
+/// A filled rectangle drawn on the screen, by its top left corner and its size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RectDraw {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl From<&RectFull> for RectDraw {
+    fn from(rect: &RectFull) -> RectDraw {
+        RectDraw {
+            x: rect.pos.0,
+            y: rect.pos.1,
+            width: rect.width,
+            height: rect.height,
+        }
+    }
+}
+
+/// What is on the screen: its rows, then its icons, then its rectangles.
+type Frame = (Vec<Row>, Vec<IconDraw>, Vec<RectDraw>);
+
+/// Button events still to be returned by `get_event`.
+type Script = VecDeque<Option<ButtonEvent>>;
+
+std::thread_local! {
+    static SCREEN: RefCell<Frame> = const { RefCell::new((Vec::new(), Vec::new(), Vec::new())) };
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+    static EVENTS: RefCell<Script> = const { RefCell::new(VecDeque::new()) };
+    static PLAYING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Start over with a blank screen and no screens recorded, and have `get_event` return
+/// `events` in order, `None` standing for a tick. Once they are used up, `get_event` panics,
+/// so that a prompt waiting for more input fails the test rather than hanging it; `play`
+/// turns that into an error instead.
+pub fn script(events: impl IntoIterator<Item = Option<ButtonEvent>>) {
+    SCREEN.with(|screen| *screen.borrow_mut() = Default::default());
+    FRAMES.with(|frames| frames.borrow_mut().clear());
+    EVENTS.with(|queue| *queue.borrow_mut() = events.into_iter().collect());
+}
+
+/// The prompt run by `play` was still waiting for input when the script ran out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScriptRanOut;
+
+/// Set the script to `events`, as `script` does, and run `prompt` through it, returning what
+/// it returns, or `ScriptRanOut` if it asks for another event once they are used up.
+pub fn play<R>(
+    events: impl IntoIterator<Item = Option<ButtonEvent>>,
+    prompt: impl FnOnce() -> R,
+) -> Result<R, ScriptRanOut> {
+    script(events);
+    PLAYING.with(|playing| playing.set(true));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(prompt));
+    PLAYING.with(|playing| playing.set(false));
+    match result {
+        Ok(result) => Ok(result),
+        Err(payload) if payload.is::<ScriptRanOut>() => Err(ScriptRanOut),
+        Err(payload) => std::panic::resume_unwind(payload),
+    }
+}
+
+/// The rows on the screen now.
+pub fn screen() -> Vec<Row> {
+    SCREEN.with(|screen| screen.borrow().0.clone())
+}
+
+/// The icons on the screen now.
+pub fn icons() -> Vec<IconDraw> {
+    SCREEN.with(|screen| screen.borrow().1.clone())
+}
+
+/// The rectangles on the screen now.
+pub fn rects() -> Vec<RectDraw> {
+    SCREEN.with(|screen| screen.borrow().2.clone())
+}
+
+/// Whether nothing is drawn in `frame`.
+fn is_blank(frame: &Frame) -> bool {
+    frame.0.is_empty() && frame.1.is_empty() && frame.2.is_empty()
+}
+
+/// Every screen drawn since `script`, as what was on it when it was cleared, followed by the
+/// screen now if anything is on it.
+fn all_frames() -> Vec<Frame> {
+    let mut frames = FRAMES.with(|frames| frames.borrow().clone());
+    let screen = SCREEN.with(|screen| screen.borrow().clone());
+    if !is_blank(&screen) {
+        frames.push(screen);
+    }
+    frames
+}
+
+/// The rows of every screen drawn since `script`, as they were when it was cleared, followed
+/// by the screen now if anything is on it.
+pub fn frames() -> Vec<Vec<Row>> {
+    all_frames().into_iter().map(|(rows, _, _)| rows).collect()
+}
+
+/// The icons of the same screens as `frames`.
+pub fn frame_icons() -> Vec<Vec<IconDraw>> {
+    all_frames()
+        .into_iter()
+        .map(|(_, icons, _)| icons)
+        .collect()
+}
+
+pub fn clear_screen() {
+    let frame = SCREEN.with(|screen| core::mem::take(&mut *screen.borrow_mut()));
+    if !is_blank(&frame) {
+        FRAMES.with(|frames| frames.borrow_mut().push(frame));
+    }
+}
+
+pub fn get_event(_buttons: &mut ButtonsState) -> Option<ButtonEvent> {
+    match EVENTS.with(|queue| queue.borrow_mut().pop_front()) {
+        Some(event) => event,
+        // Unwind without a panic message, for `play` to catch
+        None if PLAYING.with(Cell::get) => std::panic::resume_unwind(Box::new(ScriptRanOut)),
+        None => panic!("Button script ran out"),
+    }
+}
+
+fn record(text: &str, loc: Location, layout: Layout, bold: bool) {
+    let row = Row {
+        x: layout.get_x(text.compute_width(bold)),
+        y: loc.get_y(TEXT_ROW_HEIGHT),
+        text: text.to_string(),
+        bold,
+    };
+    SCREEN.with(|screen| screen.borrow_mut().0.push(row));
+}
+
+/// Like the SDK's `Draw`, recording icons and rectangles instead of drawing them.
+pub trait Draw {
+    fn display(&self);
+    fn erase(&self);
+
+    fn instant_display(&self) {
+        self.display();
+    }
+
+    fn instant_erase(&self) {
+        self.erase();
+    }
+}
+
+impl Draw for Icon<'_> {
+    fn display(&self) {
+        SCREEN.with(|screen| screen.borrow_mut().1.push(self.into()));
+    }
+
+    fn erase(&self) {
+        let drawn = IconDraw::from(self);
+        SCREEN.with(|screen| screen.borrow_mut().1.retain(|icon| *icon != drawn));
+    }
+}
+
+impl Draw for RectFull {
+    fn display(&self) {
+        SCREEN.with(|screen| screen.borrow_mut().2.push(self.into()));
+    }
+
+    fn erase(&self) {
+        let drawn = RectDraw::from(self);
+        SCREEN.with(|screen| screen.borrow_mut().2.retain(|rect| *rect != drawn));
+    }
+}
+
+/// The width of a char as the harness measures it, in pixels; bold chars are one wider.
+pub const CHAR_WIDTH: usize = 6;
+
+/// Like the SDK's `StringPlace`, recording text instead of drawing it.
+pub trait StringPlace {
+    fn compute_width(&self, bold: bool) -> usize;
+    fn place(&self, loc: Location, layout: Layout, bold: bool);
+}
+
+impl StringPlace for &str {
+    fn compute_width(&self, bold: bool) -> usize {
+        self.chars().count() * (CHAR_WIDTH + usize::from(bold))
+    }
+
+    fn place(&self, loc: Location, layout: Layout, bold: bool) {
+        record(self, loc, layout, bold);
+    }
+}
+
+/// Like the SDK's `Label`, recording its text instead of drawing it.
+pub struct Label<'a> {
+    text: &'a str,
+    loc: Location,
+}
+
+impl<'a> From<&'a str> for Label<'a> {
+    fn from(text: &'a str) -> Label<'a> {
+        Label {
+            text,
+            loc: Location::Middle,
+        }
+    }
+}
+
+impl<'a> Label<'a> {
+    pub fn location(self, loc: Location) -> Label<'a> {
+        Label { loc, ..self }
+    }
+
+    pub fn display(&self) {
+        record(self.text, self.loc, Layout::Centered, false);
+    }
+
+    pub fn instant_display(&self) {
+        self.display();
+    }
+}
+
+/// Like the SDK's `MessageValidator`, recording its lines instead of drawing them: the message
+/// on the first page, then the confirm lines on the next and the cancel lines on the last. The
+/// left and right buttons move between pages, and both buttons answer on the last two.
+pub struct MessageValidator<'a> {
+    message: &'a [&'a str],
+    confirm: &'a [&'a str],
+    cancel: &'a [&'a str],
+}
+
+impl<'a> MessageValidator<'a> {
+    pub const fn new(
+        message: &'a [&'a str],
+        confirm: &'a [&'a str],
+        cancel: &'a [&'a str],
+    ) -> Self {
+        MessageValidator {
+            message,
+            confirm,
+            cancel,
+        }
+    }
+
+    pub fn ask(&self) -> bool {
+        let pages = [self.message, self.confirm, self.cancel];
+        let draw = |page: usize| {
+            clear_screen();
+            for (i, line) in pages[page].iter().enumerate() {
+                let loc = Location::Custom(i * TEXT_ROW_HEIGHT);
+                record(line, loc, Layout::Centered, page > 0);
+            }
+        };
+        let mut buttons = ButtonsState::new();
+        let mut page = 0;
+        draw(page);
+        loop {
+            match get_event(&mut buttons) {
+                Some(ButtonEvent::LeftButtonRelease) => {
+                    page = page.saturating_sub(1);
+                    draw(page);
+                }
+                Some(ButtonEvent::RightButtonRelease) => {
+                    page = (page + 1).min(pages.len() - 1);
+                    draw(page);
+                }
+                Some(ButtonEvent::BothButtonsRelease) if page > 0 => return page == 1,
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ledger_device_sdk::buttons::ButtonEvent::*;
+
+    #[test]
+    fn auto_confirm_waits_on_a_single_page() {
+        let scroller = WriteScroller::<_, 16>::new("Amount", |w| Ok(w.write_str("1 BTC")?))
+            .with_end_behavior(EndBehavior::AutoConfirm);
+        let events = [None, None, Some(BothButtonsPress), Some(BothButtonsRelease)];
+        assert!(matches!(
+            play(events, || scroller.run()),
+            Ok(PromptOutcome::Rejected)
+        ));
+        assert_eq!(frames()[0][1].text, "1 BTC");
+    }
+
+    fn texts(rows: &[Row]) -> Vec<&str> {
+        rows.iter().map(|row| row.text.as_str()).collect()
+    }
+
+    #[test]
+    fn run_draws_each_page_and_its_arrows() {
+        let scroller =
+            WriteScroller::<_, 16>::new("Address", |w| Ok(w.write_str("0123456789abcdefghij")?))
+                .with_show_index(true);
+        let events = [
+            Some(RightButtonPress),
+            Some(RightButtonRelease),
+            Some(RightButtonPress),
+            Some(RightButtonRelease),
+        ];
+        assert_eq!(play(events, || scroller.run()), Ok(PromptOutcome::Accepted));
+        let frames = frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(texts(&frames[0]), ["Address (1/2)", "0123456789abcdef"]);
+        assert_eq!(texts(&frames[1]), ["Address (2/2)", "ghij"]);
+        let icons = frame_icons();
+        assert_eq!(icons[0][0], IconDraw::from(&RIGHT_ARROW));
+        assert_eq!(
+            icons[1][..2],
+            [IconDraw::from(&LEFT_ARROW), IconDraw::from(&RIGHT_CHECK)]
+        );
+    }
+
+    #[test]
+    fn review_has_no_field_past_the_last() {
+        let fields = [review::Field {
+            title: "Amount",
+            contents: &|w| Ok(w.write_str("1 BTC")?),
+        }];
+        let mut review = review::Review::new(&fields);
+        assert_eq!(play([], || review.ask_field(1)), Ok(None));
+        assert!(frames().is_empty());
+    }
+
+    #[test]
+    fn select_from_goes_round_to_cancel() {
+        let options = [("Low", 1), ("High", 2)];
+        let both = [Some(BothButtonsPress), Some(BothButtonsRelease)];
+        let right = [Some(RightButtonPress), Some(RightButtonRelease)];
+        let events = right.iter().chain(&both).copied();
+        assert_eq!(play(events, || select_from("Fee", &options)), Ok(Some(2)));
+        assert_eq!(texts(&frames()[1]), ["Fee", "High"]);
+        let left = [Some(LeftButtonPress), Some(LeftButtonRelease)];
+        let events = left.iter().chain(&both).copied();
+        assert_eq!(play(events, || select_from("Fee", &options)), Ok(None));
+        assert_eq!(texts(&screen()), ["Cancel"]);
+        assert_eq!(play([], || select_from::<u8>("Fee", &[])), Ok(None));
+        // Labels may be borrowed, e.g. from a request
+        let label = std::string::String::from("Medium");
+        let events = both.iter().copied();
+        let picked = play(events, || select_from(&label[..3], &[(&label[..], 3)]));
+        assert_eq!(picked, Ok(Some(3)));
+        assert_eq!(texts(&frames()[0]), ["Med", "Medium"]);
+    }
+
+    #[test]
+    fn sections_break_rows_at_lines_and_chars() {
+        let sections = [
+            Section {
+                header: "Recipient",
+                contents: &|w| Ok(w.write_str("Alice\nBob")?),
+            },
+            Section {
+                header: "Note",
+                contents: &|w| Ok(w.write_str("ééééééééé")?),
+            },
+        ];
+        let scroller = SectionScroller::<16>::new("Memo", &sections);
+        let events = [
+            Some(RightButtonPress),
+            Some(RightButtonRelease),
+            Some(RightButtonPress),
+            Some(RightButtonRelease),
+        ];
+        assert_eq!(play(events, || scroller.ask(false)), Ok(true));
+        let frames = frames();
+        assert_eq!(texts(&frames[0]), ["Memo", "Recipient", "Alice", "Bob"]);
+        assert_eq!(texts(&frames[1]), ["Memo", "Note", "éééééééé", "é"]);
+    }
+
+    #[test]
+    fn script_running_out_is_an_error() {
+        let scroller = WriteScroller::<_, 16>::new("Amount", |w| Ok(w.write_str("1 BTC")?));
+        let events = [Some(RightButtonPress)];
+        assert_eq!(play(events, || scroller.run()), Err(ScriptRanOut));
+        assert_eq!(texts(&screen()), ["Amount", "1 BTC"]);
+        assert!(icons().contains(&IconDraw::from(&RIGHT_CHECK)));
+    }
+
+    /// Check that moving right through `scroller` draws as many pages as it counts, the last
+    /// move accepting it, in single-row mode and in three-row mode.
+    fn assert_counted_pages_drawn<S: PromptSource<16>>(scroller: WriteScroller<'_, S, 16>) {
+        let taps = |count: usize| {
+            (0..count).flat_map(|_| [Some(RightButtonPress), Some(RightButtonRelease)])
+        };
+        let count = scroller.page_count().ok().unwrap();
+        assert_eq!(
+            play(taps(count), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        assert_eq!(frames().len(), count);
+
+        let scroller = scroller.with_three_rows(true);
+        let count = scroller.page_count_three_rows().ok().unwrap();
+        assert_eq!(
+            play(taps(count), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        assert_eq!(frames().len(), count);
+    }
+
+    #[test]
+    fn page_count_is_the_pages_drawn() {
+        assert_counted_pages_drawn(WriteScroller::<_, 16>::new("Data", |w| {
+            Ok(w.write_str("0123456789abcdefghijklmnopqrstuvwxyzABCD")?)
+        }));
+        assert_counted_pages_drawn(WriteScroller::<_, 16>::new("Memo", |w| {
+            Ok(w.write_str("one\ntwo\n\nthree")?)
+        }));
+        assert_counted_pages_drawn(WriteScroller::<_, 16>::new("Name", |w| {
+            Ok(w.write_str("ééééééééééééééééééé")?)
+        }));
+        assert_counted_pages_drawn(
+            WriteScroller::<_, 16>::new("Memo", |w| {
+                Ok(w.write_str("the quick brown fox jumps over the lazy dog")?)
+            })
+            .with_word_wrap(true),
+        );
+        assert_counted_pages_drawn(WriteScroller::<_, 16>::new("Data", |_| Ok(())));
+    }
+
+    /// A tap of the right button, then the ticks for which its release is held back to be
+    /// coalesced with the other button, then both buttons.
+    fn held_back_tap() -> Vec<Option<ButtonEvent>> {
+        let mut events = Vec::from([Some(RightButtonPress), Some(RightButtonRelease)]);
+        events.extend([None; 12]);
+        events.extend([Some(BothButtonsPress), Some(BothButtonsRelease)]);
+        events
+    }
+
+    fn alphabet() -> WriteScroller<'static, impl PromptSource<16>, 16> {
+        WriteScroller::<_, 16>::new("Data", |w| {
+            Ok(w.write_str("abcdefghijklmnopqrstuvwxyzABCDEFGHIJ")?)
+        })
+        .with_both_buttons_window(10)
+    }
+
+    #[test]
+    fn held_back_tap_is_not_a_long_press() {
+        let scroller = alphabet().with_long_press_jump(true);
+        let outcome = play(held_back_tap(), || scroller.run());
+        assert!(matches!(outcome, Ok(PromptOutcome::Rejected)));
+        assert_eq!(screen()[1].text, "qrstuvwxyzABCDEF");
+    }
+
+    #[test]
+    fn both_buttons_down_is_not_idle() {
+        let scroller =
+            WriteScroller::<_, 16>::new("Amount", |w| Ok(w.write_str("1 BTC")?)).with_timeout(3);
+        let events = [None, None, Some(BothButtonsPress), None, None];
+        let outcome = play(events, || scroller.run());
+        assert_eq!(outcome, Err(ScriptRanOut));
+    }
+
+    #[test]
+    fn held_back_release_is_not_idle() {
+        let scroller = alphabet().with_timeout(3);
+        let events = [
+            None,
+            Some(RightButtonPress),
+            Some(RightButtonRelease),
+            None,
+            None,
+        ];
+        let outcome = play(events, || scroller.run());
+        assert_eq!(outcome, Err(ScriptRanOut));
+        let events = [
+            None,
+            Some(RightButtonPress),
+            Some(RightButtonRelease),
+            None,
+            None,
+            None,
+        ];
+        let outcome = play(events, || scroller.run());
+        assert_eq!(outcome, Ok(PromptOutcome::Rejected));
+    }
+
+    #[test]
+    fn three_rows_writes_the_content_once_per_page() {
+        let writes = core::cell::Cell::new(0);
+        let scroller = WriteScroller::<_, 16>::new("Log", |w| {
+            writes.set(writes.get() + 1);
+            Ok(w.write_str("one\ntwo\nthree\nfour\nfive\nsix\nseven")?)
+        })
+        .with_three_rows(true);
+        assert!(matches!(scroller.page_count_three_rows(), Ok(3)));
+        let layout_writes = writes.replace(0);
+        let events = [
+            Some(RightButtonPress),
+            Some(RightButtonRelease),
+            Some(RightButtonPress),
+            Some(RightButtonRelease),
+            Some(BothButtonsPress),
+            Some(BothButtonsRelease),
+        ];
+        assert_eq!(play(events, || scroller.run()), Ok(PromptOutcome::Rejected));
+        assert_eq!(screen()[1].text, "seven");
+        assert_eq!(writes.get(), layout_writes + 3);
+    }
+
+    #[test]
+    fn held_back_tap_does_not_repeat() {
+        let scroller = alphabet().with_auto_repeat(4, 2);
+        let outcome = play(held_back_tap(), || scroller.run());
+        assert!(matches!(outcome, Ok(PromptOutcome::Rejected)));
+        assert_eq!(screen()[1].text, "qrstuvwxyzABCDEF");
+    }
+
+    #[test]
+    fn message_validator_answers_on_its_last_two_pages() {
+        let validator = MessageValidator::new(&["Sign", "message?"], &["Sign"], &["Reject"]);
+        let right = [Some(RightButtonPress), Some(RightButtonRelease)];
+        let both = [Some(BothButtonsPress), Some(BothButtonsRelease)];
+        let events = both.iter().chain(&right).chain(&both).copied();
+        assert_eq!(play(events, || validator.ask()), Ok(true));
+        assert_eq!(texts(&frames()[0]), ["Sign", "message?"]);
+        let events = right
+            .iter()
+            .chain(&right)
+            .chain(&right)
+            .chain(&both)
+            .copied();
+        assert_eq!(play(events, || validator.ask()), Ok(false));
+        assert_eq!(texts(&screen()), ["Reject"]);
+    }
+
+    #[test]
+    fn text_is_measured_by_its_chars() {
+        assert_eq!("Détails".compute_width(false), 7 * CHAR_WIDTH);
+        assert_eq!("Détails".compute_width(true), 7 * (CHAR_WIDTH + 1));
+    }
+
+    /// Taps of the right button, `count` of them.
+    fn rights(count: usize) -> impl Iterator<Item = Option<ButtonEvent>> {
+        (0..count).flat_map(|_| [Some(RightButtonPress), Some(RightButtonRelease)])
+    }
+
+    /// A press and release of both buttons.
+    fn both() -> impl Iterator<Item = Option<ButtonEvent>> {
+        [Some(BothButtonsPress), Some(BothButtonsRelease)]
+            .iter()
+            .copied()
+    }
+
+    #[test]
+    fn three_rows_more_icon_is_drawn_until_the_last_page() {
+        let scroller =
+            WriteScroller::<_, 16>::new("Log", |w| Ok(w.write_str("one\ntwo\nthree\nfour\nfive")?))
+                .with_three_rows(true)
+                .with_three_rows_more_icon(DOWN_ARROW);
+        assert_eq!(
+            play(rights(2), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        let icons = frame_icons();
+        assert!(icons[0].contains(&IconDraw::from(&DOWN_ARROW)));
+        assert!(!icons[0].contains(&IconDraw::from(&RIGHT_ARROW)));
+        assert!(!icons[1].contains(&IconDraw::from(&DOWN_ARROW)));
+        assert!(icons[1].contains(&IconDraw::from(&RIGHT_CHECK)));
+    }
+
+    #[test]
+    fn ellipsis_grows_in_place() {
+        let mut animation = EllipsisAnimation::new("Signing");
+        let frames_after_ticks = play([], || {
+            animation.show();
+            [(); 3].map(|()| {
+                animation.tick();
+                animation.frame()
+            })
+        });
+        assert_eq!(frames_after_ticks, Ok([1, 2, 0]));
+        let shown: Vec<_> = frames().iter().map(|rows| rows[0].text.clone()).collect();
+        assert_eq!(
+            shown,
+            ["Signing.  ", "Signing.. ", "Signing...", "Signing.  "]
+        );
+        assert_eq!(animation.frame(), 0);
+    }
+
+    #[test]
+    fn high_fee_is_warned_about_first() {
+        let fee = || prompts::confirm_fee(150_000, 8, "BTC", 100_000);
+        assert_eq!(play(rights(2), fee), Ok(Some(())));
+        let shown = frames();
+        assert_eq!(texts(&shown[0]), ["High fee"]);
+        assert_eq!(texts(&shown[1]), ["Fee", "0.0015 BTC"]);
+        let both = [Some(BothButtonsPress), Some(BothButtonsRelease)];
+        assert_eq!(play(both, fee), Ok(None));
+        assert_eq!(frames().len(), 1);
+        let low_fee = || prompts::confirm_fee(150_000, 8, "BTC", 150_000);
+        assert_eq!(play(rights(1), low_fee), Ok(Some(())));
+        assert_eq!(texts(&frames()[0]), ["Fee", "0.0015 BTC"]);
+    }
+
+    #[test]
+    fn page_per_section_starts_each_header_on_a_page() {
+        let sections = [
+            Section {
+                header: "From",
+                contents: &|w| Ok(w.write_str("Alice")?),
+            },
+            Section {
+                header: "To",
+                contents: &|w| Ok(w.write_str("Bob\nCarol\nDave")?),
+            },
+        ];
+        let scroller =
+            SectionScroller::<16>::new("Transfer", &sections).with_page_per_section(true);
+        assert_eq!(play(rights(3), || scroller.ask(false)), Ok(true));
+        let frames = frames();
+        assert_eq!(texts(&frames[0]), ["Transfer", "From", "Alice"]);
+        assert_eq!(texts(&frames[1]), ["Transfer", "To", "Bob", "Carol"]);
+        assert_eq!(texts(&frames[2]), ["Transfer", "Dave"]);
+    }
+
+    #[test]
+    fn run_at_keeps_the_page_and_run_decision_goes_back() {
+        let scroller = alphabet().with_both_buttons_window(0);
+        let mut cursor = 0;
+        let both = [Some(BothButtonsPress), Some(BothButtonsRelease)];
+        let events = rights(1).chain(both);
+        let outcome = play(events, || scroller.run_at(&mut cursor));
+        assert_eq!(outcome, Ok(PromptOutcome::Rejected));
+        assert_eq!(cursor, 1);
+        assert_eq!(
+            play(rights(2), || scroller.run_at(&mut cursor)),
+            Ok(PromptOutcome::Accepted)
+        );
+        assert_eq!(cursor, 2);
+
+        let scroller = scroller.with_exit_on_left_at_start(true);
+        let left = [Some(LeftButtonPress), Some(LeftButtonRelease)];
+        assert_eq!(
+            play(left, || scroller.run_decision()),
+            Ok(Ok(Decision::Back))
+        );
+        assert_eq!(play(left, || scroller.run()), Ok(PromptOutcome::Rejected));
+        let mut events = left.iter().chain(&left).copied();
+        let mut cursor = 1;
+        let decision = scroller.run_with_events(&mut cursor, || events.next().unwrap());
+        assert_eq!(decision, Ok(Decision::Back));
+        assert_eq!(cursor, 0);
+    }
+
+    #[cfg(feature = "jog")]
+    #[test]
+    fn jog_pages_as_the_buttons_do() {
+        let scroller = alphabet().with_both_buttons_window(0);
+        let left = [Some(LeftButtonPress), Some(LeftButtonRelease)];
+        let events = rights(1).chain(left).chain(rights(3));
+        assert_eq!(play(events, || scroller.run()), Ok(PromptOutcome::Accepted));
+        let button_frames = frames();
+        assert_eq!(button_frames.len(), 5);
+
+        use JogEvent::*;
+        let mut intents = [Forward, Backward, Forward, Forward, Press]
+            .iter()
+            .copied()
+            .map(NavIntent::from);
+        script([]);
+        let decision = scroller.run_with_intents(&mut 0, || Some(intents.next().unwrap()));
+        assert_eq!(decision, Ok(Decision::Accept));
+        assert_eq!(frames(), button_frames);
+    }
+
+    #[test]
+    fn subtitle_stays_on_every_page() {
+        let scroller = WriteScroller::<_, 16>::new("Transfer", |w| {
+            Ok(w.write_str("To Alice\none\ntwo\nthree")?)
+        })
+        .with_three_rows(true)
+        .with_first_line_as_subtitle(true);
+        assert_eq!(
+            play(rights(2), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        let frames = frames();
+        assert_eq!(texts(&frames[0]), ["Transfer", "To Alice", "one", "two"]);
+        assert_eq!(texts(&frames[1]), ["Transfer", "To Alice", "three"]);
+        assert!(frames.iter().all(|rows| rows[1].bold));
+    }
+
+    #[test]
+    fn both_buttons_act_as_configured() {
+        let scroller = alphabet()
+            .with_both_buttons_window(0)
+            .with_both_buttons(BothButtonAction::ConfirmOnLast);
+        let events = both().chain(rights(2)).chain(both());
+        assert_eq!(play(events, || scroller.run()), Ok(PromptOutcome::Accepted));
+        assert_eq!(texts(&screen())[1], "GHIJ");
+
+        let scroller = scroller.with_both_buttons(BothButtonAction::Ignore);
+        let events = rights(2).chain(both()).chain(rights(1));
+        assert_eq!(play(events, || scroller.run()), Ok(PromptOutcome::Accepted));
+        let events = rights(2).chain(both());
+        assert_eq!(play(events, || scroller.run()), Err(ScriptRanOut));
+    }
+
+    #[test]
+    fn review_list_keeps_a_running_total() {
+        let items = [
+            prompts::ListItem {
+                label: "Alice",
+                amount: 5,
+            },
+            prompts::ListItem {
+                label: "Bob",
+                amount: 7,
+            },
+        ];
+        let review = || prompts::review_list("Send", &items, 0, "ATOM");
+        assert_eq!(play(rights(5), review), Ok(Some(())));
+        let frames = frames();
+        assert_eq!(
+            texts(&frames[0]),
+            ["Send", "Alice", "5 ATOM", "Running total"]
+        );
+        assert_eq!(texts(&frames[1]), ["Send", "5 ATOM"]);
+        assert_eq!(texts(&frames[3]), ["Send", "12 ATOM"]);
+        assert_eq!(texts(&frames[4]), ["Total", "12 ATOM"]);
+        let overflowing = [
+            prompts::ListItem {
+                label: "Alice",
+                amount: u128::MAX,
+            },
+            prompts::ListItem {
+                label: "Bob",
+                amount: 1,
+            },
+        ];
+        let review = || prompts::review_list("Send", &overflowing, 0, "ATOM");
+        assert_eq!(play(rights(3), review), Ok(None));
+    }
+
+    #[test]
+    fn cursor_starts_on_the_page_left_at() {
+        let scroller = WriteScroller::<_, 16>::new("Log", |w| {
+            Ok(w.write_str("one\ntwo\nthree\nfour\nfive\nsix\nseven")?)
+        })
+        .with_three_rows(true);
+        let mut cursor = 1;
+        let events = rights(1).chain(both());
+        assert_eq!(
+            play(events, || scroller.run_at(&mut cursor)),
+            Ok(PromptOutcome::Rejected)
+        );
+        assert_eq!(texts(&frames()[0])[1..], ["four", "five", "six"]);
+        assert_eq!(cursor, 2);
+        // A cursor past the end starts on the last page
+        let mut cursor = 10;
+        assert_eq!(
+            play(rights(1), || scroller.run_at(&mut cursor)),
+            Ok(PromptOutcome::Accepted)
+        );
+        assert_eq!(texts(&frames()[0])[1..], ["seven"]);
+    }
+
+    #[test]
+    fn unexpected_nonce_is_warned_about() {
+        let nonce = || prompts::confirm_nonce("Nonce", 7, Some(5));
+        assert_eq!(play(rights(2), nonce), Ok(Some(())));
+        let shown = frames();
+        assert_eq!(texts(&shown[0]), ["Unexpected nonce"]);
+        assert_eq!(texts(&shown[1]), ["Nonce", "7 (expected 5)"]);
+        let nonce = || prompts::confirm_nonce("Nonce", 5, Some(5));
+        assert_eq!(play(rights(1), nonce), Ok(Some(())));
+        assert_eq!(texts(&frames()[0]), ["Nonce", "5 (expected 5)"]);
+        let nonce = || prompts::confirm_nonce("Nonce", 5, None);
+        assert_eq!(play(both(), nonce), Ok(None));
+        assert_eq!(texts(&frames()[0]), ["Nonce", "5"]);
+    }
+
+    #[test]
+    fn entered_value_is_shown_then_confirmed() {
+        let confirm = || prompts::confirm_value_match("Amount", "42");
+        // Past the value, then on to the confirm page of the question
+        let events = rights(2).chain(both());
+        assert_eq!(play(events, confirm), Ok(Decision::Accept));
+        let shown = frames();
+        assert_eq!(texts(&shown[0]), ["Amount", "42"]);
+        assert_eq!(texts(&shown[1]), ["Does it match?"]);
+        assert_eq!(texts(&shown[2]), ["Yes, it matches"]);
+        let events = rights(3).chain(both());
+        assert_eq!(play(events, confirm), Ok(Decision::Reject));
+        assert_eq!(play(both(), confirm), Ok(Decision::Reject));
+        assert_eq!(frames().len(), 1);
+    }
+
+    #[test]
+    fn risky_last_page_is_marked() {
+        let flags = RiskFlags {
+            high_fee: true,
+            ..RiskFlags::default()
+        };
+        assert_eq!(flags.level(), RiskLevel::Elevated);
+        assert_eq!(RiskFlags::default().level(), RiskLevel::Normal);
+        let scroller = alphabet()
+            .with_both_buttons_window(0)
+            .with_risk(flags.level());
+        assert_eq!(
+            play(rights(3), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        let shown = frames();
+        let icons = frame_icons();
+        assert!(!shown[1][0].bold && !icons[1].contains(&IconDraw::from(&RIGHT_WARNING)));
+        assert!(shown[2][0].bold && icons[2].contains(&IconDraw::from(&RIGHT_WARNING)));
+    }
+
+    #[test]
+    fn idle_dot_bounces_between_its_ends() {
+        let mut animation = IdleAnimation::new();
+        let span = (IDLE_DOT_MAX_X - IDLE_DOT_MIN_X) as usize;
+        let positions = play([], || {
+            animation.display();
+            (0..2 * span)
+                .map(|_| {
+                    animation.tick();
+                    animation.position()
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap();
+        assert_eq!(positions[span - 1], IDLE_DOT_MAX_X);
+        assert_eq!(positions[2 * span - 1], IDLE_DOT_MIN_X);
+        assert!(positions
+            .windows(2)
+            .all(|pair| (pair[0] - pair[1]).abs() == 1));
+        assert_eq!(
+            icons(),
+            [IconDraw::from(&IDLE_DOT_ICON.set_x(IDLE_DOT_MIN_X))]
+        );
+    }
+
+    #[test]
+    fn errors_are_shown_by_their_message() {
+        let scroller = WriteScroller::<_, 16>::new("Data", |w| Ok(w.write_str("a")?))
+            .with_known_length(16 * (MAX_PAGE_COUNT + 1));
+        let outcome = play([], || scroller.run());
+        assert_eq!(
+            outcome,
+            Ok(PromptOutcome::Errored(ScrollerError::TooManyPages))
+        );
+        assert!(frames().is_empty());
+        let events = [Some(RightButtonPress), Some(RightButtonRelease)];
+        let shown = play(events, || show_error(&ScrollerError::TooManyPages));
+        assert_eq!(shown, Ok(()));
+        assert_eq!(texts(&screen()), ["Too many pages"]);
+    }
+
+    /// The small digit glyphs drawn in `icons`, as the number they show.
+    fn small_digits(icons: &[IconDraw]) -> String {
+        icons
+            .iter()
+            .filter_map(|icon| {
+                if icon.bitmap == bitmaps::SMALL_SLASH_GLYPH.bitmap {
+                    return Some('/');
+                }
+                let mut glyphs = bitmaps::SMALL_DIGIT_GLYPHS.iter();
+                let digit = glyphs.position(|glyph| glyph.bitmap == icon.bitmap)?;
+                char::from_digit(digit as u32, 10)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn glyph_counter_keeps_clear_of_the_title() {
+        let text = "0123456789abcdef".repeat(12);
+        let scroller = WriteScroller::<_, 16>::new("Receive address", |w| Ok(w.write_str(&text)?))
+            .with_show_index(true)
+            .with_page_counter(PageCounter::Glyphs);
+        assert_eq!(
+            play(rights(12), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        let (shown, icons) = (frames(), frame_icons());
+        assert_eq!(small_digits(&icons[0]), "1/12");
+        assert_eq!(small_digits(&icons[9]), "10/12");
+        // Past the counter, which ends at x = 21, and centered on the rest of the row
+        let title = &shown[9][0];
+        assert_eq!((title.text.as_str(), title.x), ("Receive address", 30));
+
+        let scroller =
+            WriteScroller::<_, 16>::new("Destination wallet", |w| Ok(w.write_str(&text)?))
+                .with_show_index(true)
+                .with_page_counter(PageCounter::Glyphs);
+        assert_eq!(
+            play(rights(12), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        let title = &frames()[9][0];
+        assert_eq!((title.text.as_str(), title.x), ("Destination w...", 27));
+    }
+
+    #[test]
+    fn long_left_press_on_the_last_page_reviews_again() {
+        let scroller = alphabet()
+            .with_both_buttons_window(0)
+            .with_review_again(true);
+        let mut events: Vec<_> = rights(2).collect();
+        events.push(Some(LeftButtonPress));
+        events.extend([None; LONG_PRESS_TICKS]);
+        events.push(Some(LeftButtonRelease));
+        events.extend(both());
+        assert_eq!(play(events, || scroller.run()), Ok(PromptOutcome::Rejected));
+        assert_eq!(texts(&screen())[1], "abcdefghijklmnop");
+
+        let events = rights(2)
+            .chain([Some(LeftButtonPress), Some(LeftButtonRelease)])
+            .chain(both());
+        assert_eq!(play(events, || scroller.run()), Ok(PromptOutcome::Rejected));
+        assert_eq!(texts(&screen())[1], "qrstuvwxyzABCDEF");
+    }
+
+    #[test]
+    fn default_choice_is_bold_and_taken_by_both_buttons() {
+        let bold = |label: &str| screen().iter().any(|row| row.text == label && row.bold);
+        let confirm = |default| move || prompts::confirm_with_default("Send?", default);
+        assert_eq!(
+            play(both(), confirm(Decision::Reject)),
+            Ok(Decision::Reject)
+        );
+        assert!(bold("Reject") && !bold("Accept"));
+        assert_eq!(
+            play(both(), confirm(Decision::Accept)),
+            Ok(Decision::Accept)
+        );
+        assert!(bold("Accept") && !bold("Reject"));
+        let events = [Some(RightButtonPress), Some(RightButtonRelease)];
+        assert_eq!(
+            play(events, confirm(Decision::Reject)),
+            Ok(Decision::Accept)
+        );
+    }
+
+    #[test]
+    fn overridden_label_is_drawn() {
+        // Tests run side by side, so only a label no other test looks for is overridden
+        static GERMAN: strings::Strings = strings::Strings {
+            vesting: "Sperrfrist",
+            ..strings::Strings::ENGLISH
+        };
+        strings::set_strings(&GERMAN);
+        let _ = play([], || prompts::confirm_vesting(0, 1, 0, "ATOM"));
+        strings::set_strings(&strings::Strings::ENGLISH);
+        assert!(frames()[0][0].text.starts_with("Sperrfrist"));
+    }
+
+    #[test]
+    fn pinned_row_stays_while_the_rest_pages() {
+        let scroller =
+            WriteScroller::<_, 16>::new("Receive", |w| Ok(w.write_str("one\ntwo\nthree\nfour")?))
+                .with_three_rows(true)
+                .with_pinned_row("Account 0");
+        assert_eq!(
+            play(rights(2), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        let frames = frames();
+        assert_eq!(texts(&frames[0]), ["Receive", "Account 0", "one", "two"]);
+        assert_eq!(texts(&frames[1]), ["Receive", "Account 0", "three", "four"]);
+    }
+
+    #[test]
+    fn terms_are_accepted_only_past_their_last_page() {
+        let terms = || prompts::accept_terms("Terms", "one\ntwo\nthree\nfour\nfive");
+        let offered = || frames().iter().any(|rows| texts(rows).contains(&"Accept"));
+        assert_eq!(play(rights(1).chain(both()), terms), Ok(Decision::Reject));
+        assert!(!offered());
+        assert_eq!(play(rights(3).chain(both()), terms), Ok(Decision::Accept));
+        assert!(offered());
+        assert_eq!(texts(&frames()[1])[1..], ["four", "five"]);
+    }
+
+    #[test]
+    fn line_numbers_go_on_across_pages() {
+        let scroller = WriteScroller::<_, 16>::new("Recipients", |w| {
+            Ok(w.write_str(&"abcdefghijklm".repeat(5))?)
+        })
+        .with_three_rows(true)
+        .with_line_numbers(true);
+        assert_eq!(
+            play(rights(2), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        let frames = frames();
+        assert_eq!(
+            texts(&frames[0])[1..],
+            ["1. abcdefghijklm", "2. abcdefghijklm", "3. abcdefghijklm"]
+        );
+        assert_eq!(
+            texts(&frames[1])[1..],
+            ["4. abcdefghijklm", "5. abcdefghijklm"]
+        );
+    }
+
+    #[test]
+    fn call_shows_its_method_and_each_param() {
+        let call = || prompts::confirm_call("transfer", &[("To", &"Alice"), ("Amount", &5)]);
+        assert_eq!(play(rights(4).chain(both()), call), Ok(Some(())));
+        let frames = frames();
+        assert_eq!(texts(&frames[0]), ["Method", "transfer"]);
+        assert_eq!(texts(&frames[1]), ["To", "Alice"]);
+        assert_eq!(texts(&frames[2]), ["Amount", "5"]);
+        assert_eq!(texts(&frames[3]), ["Confirm call"]);
+        assert_eq!(play(rights(5).chain(both()), call), Ok(None));
+    }
+
+    #[test]
+    fn side_glyph_keeps_its_area_and_the_text_pages_beside_it() {
+        let logo = ledger_device_sdk::ui::bitmaps::Glyph::new(&[7; 32], 16, 16);
+        let scroller =
+            WriteScroller::<_, 16>::new("Token", |w| Ok(w.write_str("abcdefghijklmnopqrst")?))
+                .with_side_glyph(&logo);
+        assert_eq!(
+            play(rights(2), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        let frames = frames();
+        assert_eq!(texts(&frames[1])[1], "qrst");
+        for (rows, icons) in frames.iter().zip(frame_icons()) {
+            let logo = icons.iter().find(|icon| icon.bitmap == [7; 32]).unwrap();
+            assert_eq!((logo.x, logo.width), (8, 16));
+            assert!(rows[1].x >= SIDE_GLYPH_AREA_WIDTH);
+        }
+    }
+
+    #[test]
+    fn reverse_starts_on_the_last_page() {
+        let scroller = alphabet().with_both_buttons_window(0).with_reverse(true);
+        assert_eq!(
+            play(rights(3), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        let shown: Vec<_> = frames().iter().map(|rows| rows[1].text.clone()).collect();
+        assert_eq!(shown, ["GHIJ", "qrstuvwxyzABCDEF", "abcdefghijklmnop"]);
+    }
+
+    #[test]
+    fn message_not_in_utf8_is_warned_about_and_shown_as_hex() {
+        let confirm = prompts::confirm_message;
+        assert_eq!(
+            play(rights(1), || confirm("Message", b"Hi there")),
+            Ok(Some(()))
+        );
+        assert_eq!(texts(&frames()[0]), ["Message", "Hi there"]);
+        assert_eq!(
+            play(rights(2), || confirm("Message", &[0x48, 0xff])),
+            Ok(Some(()))
+        );
+        let frames = frames();
+        assert_eq!(texts(&frames[0]), ["Not UTF-8 text"]);
+        assert_eq!(texts(&frames[1]), ["Message", "48ff"]);
+    }
+
+    #[test]
+    fn signing_request_shows_its_origin_then_its_summary() {
+        let sign = || prompts::confirm_signing("app.example", "Swap 5 ATOM");
+        assert_eq!(play(rights(3).chain(both()), sign), Ok(Decision::Accept));
+        let frames = frames();
+        assert_eq!(texts(&frames[0]), ["From", "app.example"]);
+        assert_eq!(texts(&frames[1]), ["Sign request", "Swap 5 ATOM"]);
+        assert_eq!(play(rights(4).chain(both()), sign), Ok(Decision::Reject));
+        assert_eq!(play(both(), sign), Ok(Decision::Reject));
+    }
+
+    #[test]
+    fn direction_hint_flashes_the_arrow_moved_towards() {
+        let left = IconDraw::from(&LEFT_S_ARROW);
+        let right = IconDraw::from(&RIGHT_S_ARROW);
+        let scroller = alphabet().with_both_buttons_window(0);
+        let events = || {
+            rights(1)
+                .chain([Some(LeftButtonPress), Some(LeftButtonRelease)])
+                .chain(both())
+        };
+        assert_eq!(
+            play(events(), || scroller.run()),
+            Ok(PromptOutcome::Rejected)
+        );
+        let icons = frame_icons();
+        assert!(!icons[1].contains(&right) && !icons[2].contains(&left));
+
+        let scroller = scroller.with_direction_hints(true);
+        assert_eq!(
+            play(events(), || scroller.run()),
+            Ok(PromptOutcome::Rejected)
+        );
+        let icons = frame_icons();
+        assert!(icons[1].contains(&right));
+        assert!(icons[2].contains(&left) && !icons[2].contains(&right));
+    }
+
+    #[test]
+    fn countdown_proceeds_only_when_it_runs_out() {
+        let countdown = |seconds| move || countdown_confirm("Resetting in", seconds);
+        let ticks = || core::iter::repeat_n(None, 2 * TICKS_PER_SECOND);
+        assert_eq!(play(ticks(), countdown(2)), Ok(Some(())));
+        let shown = frames();
+        let counts: Vec<_> = shown.iter().map(|rows| texts(rows)[1]).collect();
+        assert_eq!(counts, ["2...", "1..."]);
+        let events = ticks().take(5).chain([Some(LeftButtonPress)]);
+        assert_eq!(play(events, countdown(2)), Ok(None));
+        assert_eq!(play(ticks(), countdown(0)), Ok(Some(())));
+        assert_eq!(texts(&frames()[0]), ["Resetting in", "1..."]);
+
+        // Events from an app's own loop, which has passed on a tick then a press
+        let mut events = [None, Some(RightButtonPress)].iter().copied();
+        let next_event = || events.next().unwrap_or(None);
+        let outcome = play([], || {
+            countdown_confirm_with_events("Deleting in", 1, next_event)
+        });
+        assert_eq!(outcome, Ok(None));
+    }
+
+    #[test]
+    fn left_on_the_first_page_goes_back() {
+        let scroller = alphabet()
+            .with_both_buttons_window(0)
+            .with_exit_on_left_at_start(true);
+        let left = || [Some(LeftButtonPress), Some(LeftButtonRelease)];
+        let events = rights(1).chain(left()).chain(left());
+        assert_eq!(
+            play(events, || scroller.run_decision().ok()),
+            Ok(Some(Decision::Back))
+        );
+        let shown = frames();
+        assert_eq!(texts(&shown[1])[1], "qrstuvwxyzABCDEF");
+        assert_eq!(texts(&shown[2])[1], "abcdefghijklmnop");
+        assert_eq!(shown.len(), 3);
+    }
+
+    #[test]
+    fn single_releases_close_together_are_both_buttons() {
+        let scroller = alphabet();
+        let apart = |ticks| {
+            let mut events = Vec::from([Some(RightButtonPress), Some(RightButtonRelease)]);
+            events.extend(core::iter::repeat_n(None, ticks));
+            events.extend([Some(LeftButtonPress), Some(LeftButtonRelease)]);
+            events.extend([None; 12]);
+            events
+        };
+        assert_eq!(
+            play(apart(3), || scroller.run()),
+            Ok(PromptOutcome::Rejected)
+        );
+        assert_eq!(frames().len(), 1);
+        assert_eq!(play(apart(12), || scroller.run()), Err(ScriptRanOut));
+        let shown = frames();
+        assert_eq!(texts(&shown[1])[1], "qrstuvwxyzABCDEF");
+        assert_eq!(texts(&shown[2])[1], "abcdefghijklmnop");
+    }
+
+    #[test]
+    fn three_rows_end_between_groups_of_the_break_interval() {
+        let scroller = WriteScroller::<_, 16>::new("Hash", |w| {
+            Ok(w.write_str("0123456789abcdef0123456789abcdef0123456789")?)
+        })
+        .with_three_rows(true)
+        .with_break_interval(6);
+        assert_eq!(
+            play(rights(2), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        let shown = frames();
+        assert_eq!(
+            texts(&shown[0])[1..],
+            ["0123456789ab", "cdef01234567", "89abcdef0123"]
+        );
+        assert_eq!(texts(&shown[1])[1..], ["456789"]);
+    }
+
+    #[test]
+    fn title_on_the_first_page_only_leaves_its_row_to_content() {
+        let scroller = WriteScroller::<_, 16>::new("Log", |w| {
+            Ok(w.write_str("one\ntwo\nthree\nfour\nfive\nsix\nseven")?)
+        })
+        .with_three_rows(true)
+        .with_title_on_first_page_only(true);
+        assert_eq!(scroller.page_count_three_rows(), Ok(2));
+        assert_eq!(
+            play(rights(2), || scroller.run()),
+            Ok(PromptOutcome::Accepted)
+        );
+        let shown = frames();
+        assert_eq!(texts(&shown[0]), ["Log", "one", "two", "three"]);
+        assert_eq!(texts(&shown[1]), ["four", "five", "six", "seven"]);
+        assert_eq!(shown[1][0].y, TITLE_ROW_Y);
+    }
+
+    #[test]
+    fn named_address_is_accepted_from_the_last_page_of_the_address() {
+        let address = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+        let confirm = || prompts::confirm_named_address("alice.eth", address);
+        assert_eq!(play(rights(2), confirm), Err(ScriptRanOut));
+        assert_eq!(play(rights(3), confirm), Ok(Some(())));
+        let shown = frames();
+        assert_eq!(texts(&shown[0]), ["alice.eth", "0x01234...abcdef"]);
+        assert_eq!(texts(&shown[1])[1], "0x0123456789abcd");
+        assert_eq!(texts(&shown[2])[1..], ["ef0123456789abcd", "ef"]);
+    }
+
+    #[test]
+    fn transfer_to_the_same_address_is_warned_about() {
+        let transfer = |to| move || prompts::confirm_transfer("cosmos1abc", to, 5, 0, "ATOM", true);
+        assert_eq!(
+            play(rights(5).chain(both()), transfer("cosmos1abc")),
+            Ok(Some(()))
+        );
+        let shown = frames();
+        assert_eq!(texts(&shown[0]), ["Sending to self"]);
+        assert_eq!(texts(&shown[1]), ["From", "cosmos1abc"]);
+        assert_eq!(
+            play(rights(4).chain(both()), transfer("cosmos1def")),
+            Ok(Some(()))
+        );
+        let shown = frames();
+        assert_eq!(texts(&shown[0]), ["From", "cosmos1abc"]);
+        assert_eq!(texts(&shown[1]), ["To", "cosmos1def"]);
+    }
+
+    #[test]
+    fn field_past_its_page_cap_is_marked_and_acknowledged() {
+        let fields = [review::Field {
+            title: "Data",
+            contents: &|w| Ok(w.write_str("abcdefghijklmnopqrstuvwxyzABCDEFGHIJ")?),
+        }];
+        let mut review = review::Review::new(&fields).with_max_field_pages(2);
+        assert_eq!(play(rights(2), || review.ask_field(0)), Err(ScriptRanOut));
+        let shown = frames();
+        assert_eq!(texts(&shown[1])[1], "qrstuvwxyzABC...");
+        assert_eq!(texts(&screen()), ["Field truncated"]);
+        assert!(!review.is_confirmed(0));
+        assert_eq!(play(rights(3), || review.ask_field(0)), Ok(Some(())));
+        assert!(review.is_confirmed(0));
+    }
+
+    #[test]
+    fn single_row_finds_each_page_from_the_one_before() {
+        let writes = core::cell::Cell::new(0);
+        let scroller = WriteScroller::<_, 16>::new("Memo", |w| {
+            writes.set(writes.get() + 1);
+            Ok(w.write_str("one;two;three;four")?)
+        })
+        .with_page_break_char(';');
+        assert!(matches!(scroller.single_row_page_count(), Ok(4)));
+        let layout_writes = writes.replace(0);
+        let events = core::iter::repeat_n([Some(RightButtonPress), Some(RightButtonRelease)], 3)
+            .flatten()
+            .chain([Some(BothButtonsPress), Some(BothButtonsRelease)]);
+        assert_eq!(play(events, || scroller.run()), Ok(PromptOutcome::Rejected));
+        assert_eq!(texts(&frames()[3])[1], "four");
+        // The layout, the first page, then the page before and the next one for each of the rest
+        assert_eq!(writes.get(), layout_writes + 1 + 3 * 2);
+    }
+
+    #[test]
+    fn review_summary_counts_the_fields_and_answers() {
+        let approve = rights(1).chain(both());
+        let decision = play(approve, || review::review_summary(1, "Sign transaction"));
+        assert_eq!(decision, Ok(Decision::Accept));
+        assert_eq!(
+            texts(&frames()[0]),
+            ["Reviewed", "1 item", "Sign transaction"]
+        );
+        let reject = rights(2).chain(both());
+        let decision = play(reject, || review::review_summary(12, "Sign transaction"));
+        assert_eq!(decision, Ok(Decision::Reject));
+        assert_eq!(
+            texts(&frames()[0]),
+            ["Reviewed", "12 items", "Sign transaction"]
+        );
+    }
+
+    #[test]
+    fn blind_signing_is_warned_about_then_asked_for() {
+        let accept = rights(1).chain(rights(1)).chain(both());
+        assert_eq!(play(accept, prompts::blind_signing_warning), Ok(Some(())));
+        assert_eq!(texts(&frames()[0]), ["Blind signing"]);
+        assert_eq!(texts(&frames()[1]), ["Proceed with caution"]);
+        assert_eq!(play(both(), prompts::blind_signing_warning), Ok(None));
+        let reject = rights(1).chain(rights(2)).chain(both());
+        assert_eq!(play(reject, prompts::blind_signing_warning), Ok(None));
+    }
+
+    #[test]
+    fn final_accept_prompt_with_shows_the_given_labels() {
+        let prompt = || final_accept_prompt_with(&["Sign", "message?"], "Sign", "Cancel");
+        assert_eq!(play(rights(1).chain(both()), prompt), Ok(Some(())));
+        assert_eq!(texts(&frames()[1]), ["Sign"]);
+        assert_eq!(play(rights(2).chain(both()), prompt), Ok(None));
+        assert_eq!(texts(&frames()[2]), ["Cancel"]);
+    }
+
+    #[test]
+    fn yes_no_answers_with_a_single_button_only() {
+        let left = [Some(LeftButtonPress), Some(LeftButtonRelease)];
+        let ask = || prompts::ask_yes_no("Enable debug?");
+        assert_eq!(play(rights(1), ask), Ok(true));
+        assert_eq!(texts(&frames()[0]), ["Enable debug?", "No", "Yes"]);
+        assert_eq!(play(left, ask), Ok(false));
+        // Both buttons are no answer, so the prompt waits for one
+        assert_eq!(play(both().chain(left.iter().copied()), ask), Ok(false));
+        assert_eq!(play(both(), ask), Err(ScriptRanOut));
+    }
+}
@@ -4,19 +4,69 @@
 
 use arrayvec::ArrayString;
 use core::fmt::Write;
+#[cfg(feature = "trace")]
 use ledger_log::trace;
-use ledger_device_sdk::buttons::{ButtonEvent, ButtonsState};
+use ledger_device_sdk::buttons::ButtonEvent;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+use ledger_device_sdk::buttons::ButtonsState;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 use ledger_device_sdk::ui::bagls::*;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 use ledger_device_sdk::ui::layout::*;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[cfg(not(feature = "test-harness"))]
 use ledger_device_sdk::ui::gadgets::{clear_screen, get_event, MessageValidator};
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[cfg(feature = "test-harness")]
+use harness::{clear_screen, get_event, Draw, Label, MessageValidator, StringPlace};
 
+/// Without the `trace` feature, `trace!` still type-checks its arguments, so that they count
+/// as used, but formats and logs nothing.
+#[cfg(not(feature = "trace"))]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if false {
+            let _ = core::format_args!($($arg)*);
+        }
+    };
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 pub mod bitmaps;
+pub mod format;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[cfg(feature = "test-harness")]
+pub mod harness;
+#[cfg(any(target_os = "stax", target_os = "flex"))]
+mod nbgl;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub mod prompts;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[cfg(all(feature = "qr", not(target_os = "nanos")))]
+pub mod qr;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub mod review;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[cfg(feature = "host-stub")]
+pub mod snapshot;
+pub mod strings;
+
+#[cfg(any(target_os = "stax", target_os = "flex"))]
+pub use nbgl::{
+    final_accept_prompt_with, write_scroller_outcome, write_scroller_three_rows_outcome,
+};
 
 #[derive(Debug)]
 pub struct PromptWrite<'a, const N: usize> {
     offset: usize,
     buffer: &'a mut ArrayString<N>,
     total: usize,
+    chars: usize,
+    newlines: usize,
+    /// Buffers to go on writing into, in order, once `buffer` is full
+    overflow: &'a mut [ArrayString<N>],
+    /// Whether something written was cut, after which nothing more is kept
+    full: bool,
 }
 
 pub fn mk_prompt_write<'a, const N: usize>(buffer: &'a mut ArrayString<N>) -> PromptWrite<'a, N> {
@@ -24,30 +74,130 @@ pub fn mk_prompt_write<'a, const N: usize>(buffer: &'a mut ArrayString<N>) -> Pr
         offset: 0,
         buffer: buffer,
         total: 0,
+        chars: 0,
+        newlines: 0,
+        overflow: &mut [],
+        full: false,
+    }
+}
+
+impl<'a, const N: usize> PromptWrite<'a, N> {
+    /// Bytes that can still be kept, in the buffer being written and any left to go on into.
+    pub fn remaining(&self) -> usize {
+        if self.full {
+            return 0;
+        }
+        self.buffer.remaining_capacity()
+            + self
+                .overflow
+                .iter()
+                .map(|buffer| buffer.remaining_capacity())
+                .sum::<usize>()
+    }
+
+    /// Leave out the next `bytes` bytes written, as for content before the offset of a page.
+    pub fn skip(&mut self, bytes: usize) {
+        self.offset += bytes;
+    }
+
+    /// Bytes written so far, including any left out before the offset or once the buffers
+    /// were full.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Clear the buffer being written and start counting again, as for a new writer from
+    /// `mk_prompt_write`, which keeps everything from the start.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.offset = 0;
+        self.total = 0;
+        self.chars = 0;
+        self.newlines = 0;
+        self.full = false;
+    }
+}
+
+/// The largest index that is at most `i` and starts a char of `s`.
+fn floor_char_boundary(s: &str, i: usize) -> usize {
+    let mut i = core::cmp::min(i, s.len());
+    while !s.is_char_boundary(i) {
+        i -= 1;
     }
+    i
 }
 
+// Offsets and `total` count bytes, `chars` counts chars and `newlines` counts `\n`s. Cuts that
+// would fall inside a char are moved back to its start, so a char cut at the end of one page,
+// or of one buffer, starts the next. Once the last buffer is cut, later writes are only
+// counted, so that what is kept never has a gap in it.
 impl<'a, const N: usize> Write for PromptWrite<'a, N> {
     fn write_str(&mut self, s: &str) -> core::fmt::Result {
         self.total += s.len();
+        self.chars += s.chars().count();
+        self.newlines += s.bytes().filter(|b| *b == b'\n').count();
         let offset_in_s = core::cmp::min(self.offset, s.len());
         self.offset -= offset_in_s;
-        if self.offset > 0 {
+        if self.offset > 0 || self.full {
             return Ok(());
         }
-        let rv = self
-            .buffer
-            .try_push_str(
-                &s[offset_in_s
-                    ..core::cmp::min(s.len(), offset_in_s + self.buffer.remaining_capacity())],
-            )
-            .map_err(|_| core::fmt::Error);
-        rv
+        let mut rest = &s[floor_char_boundary(s, offset_in_s)..];
+        loop {
+            let end = floor_char_boundary(rest, self.buffer.remaining_capacity());
+            self.buffer
+                .try_push_str(&rest[..end])
+                .map_err(|_| core::fmt::Error)?;
+            rest = &rest[end..];
+            if rest.is_empty() {
+                return Ok(());
+            }
+            match core::mem::take(&mut self.overflow).split_first_mut() {
+                Some((next, overflow)) => {
+                    self.buffer = next;
+                    self.overflow = overflow;
+                }
+                None => {
+                    self.full = true;
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// The user's answer to a prompt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decision {
+    Accept,
+    Reject,
+    /// Leave the prompt without deciding, e.g. to go back to a menu.
+    Back,
+}
+
+impl From<bool> for Decision {
+    fn from(accepted: bool) -> Decision {
+        if accepted {
+            Decision::Accept
+        } else {
+            Decision::Reject
+        }
     }
 }
 
 pub fn final_accept_prompt(prompt: &[&str]) -> Option<()> {
-    if !MessageValidator::new(prompt, &[&"Approve"], &[&"Reject"]).ask() {
+    let strings = strings::strings();
+    final_accept_prompt_with(prompt, strings.approve, strings.reject)
+}
+
+/// Like `final_accept_prompt`, but with `confirm_label` and `reject_label` on the choices in
+/// place of the usual ones, e.g. "Sign" and "Cancel".
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn final_accept_prompt_with(
+    prompt: &[&str],
+    confirm_label: &str,
+    reject_label: &str,
+) -> Option<()> {
+    if !MessageValidator::new(prompt, &[confirm_label], &[reject_label]).ask() {
         trace!("User rejected at end\n");
         None
     } else {
@@ -56,22 +206,234 @@ pub fn final_accept_prompt(prompt: &[&str]) -> Option<()> {
     }
 }
 
-pub struct ScrollerError;
+/// Show `text` under a warning glyph. The right button continues past the warning and both
+/// buttons reject.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[inline(never)]
+pub fn warning_prompt(text: &str) -> Option<()> {
+    let mut buttons = ButtonsState::new();
+    clear_screen();
+    WARNING_ICON.instant_display();
+    text.place(Location::Custom(MENU_BOTTOM_TEXT_Y), Layout::Centered, true);
+    RIGHT_ARROW.instant_display();
+    loop {
+        match get_event(&mut buttons) {
+            Some(ButtonEvent::RightButtonPress) => {
+                RIGHT_S_ARROW.instant_display();
+            }
+            Some(ButtonEvent::RightButtonRelease) => {
+                trace!("User accepted warning");
+                break Some(());
+            }
+            Some(ButtonEvent::BothButtonsRelease) => {
+                trace!("User rejected at warning");
+                break None;
+            }
+            Some(_) | None => (),
+        }
+    }
+}
+
+/// Number of ticker events per second, with the ticker at its default period of 100ms.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub const TICKS_PER_SECOND: usize = 10;
+
+/// Show `msg` over a countdown from `seconds`, e.g. before an irreversible action. Pressing any
+/// button aborts; only a countdown that runs out without one continues. The countdown is timed
+/// by idle events, which are mostly ticker events; use `countdown_confirm_with_events` to time
+/// it by ticker events only.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn countdown_confirm(msg: &str, seconds: usize) -> Option<()> {
+    let mut buttons = ButtonsState::new();
+    countdown_confirm_with_events(msg, seconds, || get_event(&mut buttons))
+}
+
+/// Like `countdown_confirm`, but taking button events from `next_event` instead of the SDK,
+/// with `None` for a tick, e.g. from an app's own event loop that passes on only its ticker
+/// events as `None`. A countdown from 0 seconds is shown for one second, so that `msg` is seen.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[inline(never)]
+pub fn countdown_confirm_with_events(
+    msg: &str,
+    seconds: usize,
+    mut next_event: impl FnMut() -> Option<ButtonEvent>,
+) -> Option<()> {
+    for remaining in (1..=core::cmp::max(seconds, 1)).rev() {
+        clear_screen();
+        msg.place(Location::Custom(MENU_TOP_TEXT_Y), Layout::Centered, true);
+        let mut count: ArrayString<16> = ArrayString::new();
+        let _ = write!(mk_prompt_write(&mut count), "{}...", remaining);
+        count.as_str().place(
+            Location::Custom(MENU_BOTTOM_TEXT_Y),
+            Layout::Centered,
+            false,
+        );
+        let mut ticks = 0;
+        while ticks < TICKS_PER_SECOND {
+            match next_event() {
+                Some(_) => {
+                    trace!("User aborted countdown");
+                    return None;
+                }
+                None => ticks += 1,
+            }
+        }
+    }
+    Some(())
+}
+
+/// Show `text` alone in the middle of the screen, e.g. a short status such as "Done". Text
+/// that does not fit on the screen is cut short with `format::ELLIPSIS` at its end.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn show_centered(text: &str) {
+    let mut shortened: ArrayString<16> = ArrayString::new();
+    let text = if text.len() <= 16 && text_width(text, false) <= SCREEN_WIDTH {
+        text
+    } else {
+        shorten_to_fit(
+            &mut shortened,
+            text,
+            false,
+            SCREEN_WIDTH,
+            format::TruncateMode::Head,
+        );
+        shortened.as_str()
+    };
+    clear_screen();
+    text.place(Location::Middle, Layout::Centered, false);
+}
+
+/// Show `title` over how far a task with `total` steps has come, e.g. "3/10" after the third
+/// chunk of a transaction was parsed. This only draws the screen, so it is called again after
+/// each step; `current` is shown as at most `total`.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn show_progress(title: &str, current: usize, total: usize) {
+    let mut progress: ArrayString<16> = ArrayString::new();
+    let _ = write!(
+        mk_prompt_write(&mut progress),
+        "{}/{}",
+        core::cmp::min(current, total),
+        total
+    );
+    clear_screen();
+    title.place(Location::Custom(MENU_TOP_TEXT_Y), Layout::Centered, true);
+    progress.as_str().place(
+        Location::Custom(MENU_BOTTOM_TEXT_Y),
+        Layout::Centered,
+        false,
+    );
+}
+
+/// Show what went wrong in `err`, e.g. "Too many pages", under a warning glyph and wait for
+/// any button to be pressed and released.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[inline(never)]
+pub fn show_error(err: &ScrollerError) {
+    let mut message: ArrayString<16> = ArrayString::new();
+    let _ = write!(mk_prompt_write(&mut message), "{}", err);
+    trace!("Showing error: {}", message);
+    let mut buttons = ButtonsState::new();
+    clear_screen();
+    WARNING_ICON.instant_display();
+    message
+        .as_str()
+        .place(Location::Custom(MENU_BOTTOM_TEXT_Y), Layout::Centered, true);
+    loop {
+        match get_event(&mut buttons) {
+            Some(ButtonEvent::LeftButtonRelease)
+            | Some(ButtonEvent::RightButtonRelease)
+            | Some(ButtonEvent::BothButtonsRelease) => break,
+            Some(_) | None => (),
+        }
+    }
+}
+
+/// Why a prompt could not be shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollerError {
+    /// Writing the content failed, e.g. a `Display` impl returned an error.
+    Format,
+    /// The content, or bytes shown as text, are not valid UTF-8.
+    Utf8,
+    /// The content takes more than `MAX_PAGE_COUNT` pages.
+    TooManyPages,
+    /// There is more to show than the storage of the prompt holds, e.g. more than
+    /// `MAX_SECTIONS` sections.
+    Overflow,
+}
+impl core::fmt::Display for ScrollerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let strings = strings::strings();
+        f.write_str(match self {
+            ScrollerError::Format => strings.display_failed,
+            ScrollerError::Utf8 => strings.invalid_utf8,
+            ScrollerError::TooManyPages => strings.too_many_pages,
+            ScrollerError::Overflow => strings.too_much_content,
+        })
+    }
+}
 impl From<core::fmt::Error> for ScrollerError {
     fn from(_: core::fmt::Error) -> Self {
-        ScrollerError
+        ScrollerError::Format
     }
 }
 impl From<core::str::Utf8Error> for ScrollerError {
     fn from(_: core::str::Utf8Error) -> Self {
-        ScrollerError
+        ScrollerError::Utf8
     }
 }
 
+#[cfg(version("1.81"))]
+impl core::error::Error for ScrollerError {}
+
 #[cfg(not(version("1.56")))]
 impl From<core::option::NoneError> for ScrollerError {
     fn from(_: core::option::NoneError) -> Self {
-        ScrollerError
+        ScrollerError::Format
+    }
+}
+
+/// How a prompt ended, telling a rejection apart from a failure to display the prompt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PromptOutcome {
+    Accepted,
+    Rejected,
+    Errored(ScrollerError),
+}
+
+impl PromptOutcome {
+    /// `Some(())` if the prompt was accepted, as returned by `write_scroller`.
+    pub fn accepted(self) -> Option<()> {
+        match self {
+            PromptOutcome::Accepted => Some(()),
+            PromptOutcome::Rejected | PromptOutcome::Errored(_) => None,
+        }
+    }
+
+    /// Whether the prompt was accepted, or the error if it could not be shown, as returned by
+    /// `WriteScroller::ask_err`.
+    pub fn result(self) -> Result<bool, ScrollerError> {
+        match self {
+            PromptOutcome::Accepted => Ok(true),
+            PromptOutcome::Rejected => Ok(false),
+            PromptOutcome::Errored(err) => Err(err),
+        }
+    }
+}
+
+impl From<Result<bool, ScrollerError>> for PromptOutcome {
+    fn from(result: Result<bool, ScrollerError>) -> PromptOutcome {
+        match result {
+            Ok(true) => PromptOutcome::Accepted,
+            Ok(false) => {
+                trace!("User rejected prompt");
+                PromptOutcome::Rejected
+            }
+            Err(err) => {
+                trace!("Prompt failed to display: {}", err);
+                PromptOutcome::Errored(err)
+            }
+        }
     }
 }
 
@@ -81,12 +443,32 @@ pub fn write_scroller<F: for<'b> Fn(&mut PromptWrite<'b, 16>) -> Result<(), Scro
     title: &str,
     prompt_function: F,
 ) -> Option<()> {
-    if !WriteScroller::<_, 16>::new(title, prompt_function).ask(show_index) {
-        trace!("User rejected prompt");
-        None
-    } else {
-        Some(())
-    }
+    write_scroller_outcome(show_index, title, prompt_function).accepted()
+}
+
+/// Like `write_scroller`, but returning whether the prompt was accepted, or an error if the
+/// content could not be written, e.g. because `prompt_function` failed.
+pub fn write_scroller_err<F: for<'b> Fn(&mut PromptWrite<'b, 16>) -> Result<(), ScrollerError>>(
+    show_index: bool,
+    title: &str,
+    prompt_function: F,
+) -> Result<bool, ScrollerError> {
+    write_scroller_outcome(show_index, title, prompt_function).result()
+}
+
+/// Like `write_scroller`, but telling a rejection apart from an error.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[inline(never)]
+pub fn write_scroller_outcome<
+    F: for<'b> Fn(&mut PromptWrite<'b, 16>) -> Result<(), ScrollerError>,
+>(
+    show_index: bool,
+    title: &str,
+    prompt_function: F,
+) -> PromptOutcome {
+    WriteScroller::<_, 16>::new(title, prompt_function)
+        .with_show_index(show_index)
+        .run()
 }
 
 #[inline(never)]
@@ -97,26 +479,239 @@ pub fn write_scroller_three_rows<
     title: &str,
     prompt_function: F,
 ) -> Option<()> {
-    if !WriteScroller::<_, 16>::new(title, prompt_function).ask_three_rows(show_index) {
-        trace!("User rejected prompt");
-        None
+    write_scroller_three_rows_outcome(show_index, title, prompt_function).accepted()
+}
+
+/// Like `write_scroller_three_rows`, but returning errors as `write_scroller_err` does.
+pub fn write_scroller_three_rows_err<
+    F: for<'b> Fn(&mut PromptWrite<'b, 16>) -> Result<(), ScrollerError>,
+>(
+    show_index: bool,
+    title: &str,
+    prompt_function: F,
+) -> Result<bool, ScrollerError> {
+    write_scroller_three_rows_outcome(show_index, title, prompt_function).result()
+}
+
+/// Like `write_scroller`, but with the last page of the content aligned on the right, e.g. for
+/// amounts shown one after the other.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn write_scroller_right_aligned<
+    F: for<'b> Fn(&mut PromptWrite<'b, 16>) -> Result<(), ScrollerError>,
+>(
+    show_index: bool,
+    title: &str,
+    prompt_function: F,
+) -> Option<()> {
+    WriteScroller::<_, 16>::new(title, prompt_function)
+        .with_show_index(show_index)
+        .with_right_aligned(true)
+        .run()
+        .accepted()
+}
+
+/// Show a field as `title` on the top line, which stays the same on every page, and the value
+/// written by `value_fn` paged over all the rows below it, with the index of the value page in
+/// the title. Accepting or rejecting works as for `write_scroller`.
+pub fn write_field<F: for<'b> Fn(&mut PromptWrite<'b, 16>) -> Result<(), ScrollerError>>(
+    title: &str,
+    value_fn: F,
+) -> Option<()> {
+    write_scroller_three_rows(true, title, value_fn)
+}
+
+/// Like `write_scroller_three_rows`, but telling a rejection apart from an error.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[inline(never)]
+pub fn write_scroller_three_rows_outcome<
+    F: for<'b> Fn(&mut PromptWrite<'b, 16>) -> Result<(), ScrollerError>,
+>(
+    show_index: bool,
+    title: &str,
+    prompt_function: F,
+) -> PromptOutcome {
+    WriteScroller::<_, 16>::new(title, prompt_function)
+        .with_show_index(show_index)
+        .with_three_rows(true)
+        .run()
+}
+
+/// Number of pages taken by `len` chars shown `char_n` to a row and `rows` rows to a page,
+/// with every page but the last full, as for ASCII content without line breaks. Empty content
+/// still takes a page.
+pub const fn page_count(len: usize, char_n: usize, rows: usize) -> usize {
+    if len == 0 {
+        1
     } else {
-        Some(())
+        (len - 1) / (char_n * rows) + 1
+    }
+}
+
+/// Most pages a scroller shows. Content that needs more is an error, rather than a prompt
+/// that could not reasonably be paged through.
+pub const MAX_PAGE_COUNT: usize = 1000;
+
+/// Content shown by a `WriteScroller`. Closures writing the content to a `PromptWrite` are
+/// sources; other types can implement this to give the length of their content up front.
+pub trait PromptSource<const CHAR_N: usize> {
+    /// Write the content from `offset` bytes in on to `out`, which keeps what it is given
+    /// until its buffer is full. A source that cannot seek can call `out.skip(offset)` and
+    /// write the content from the start, as closures do; an offset inside a char starts at
+    /// that char.
+    fn write_page(
+        &self,
+        offset: usize,
+        out: &mut PromptWrite<'_, CHAR_N>,
+    ) -> Result<(), ScrollerError>;
+
+    /// Length of the content in bytes, if known without writing it out and the content is
+    /// ASCII without line breaks, as for `WriteScroller::with_known_length`.
+    fn len_hint(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<
+        F: for<'b> Fn(&mut PromptWrite<'b, CHAR_N>) -> Result<(), ScrollerError>,
+        const CHAR_N: usize,
+    > PromptSource<CHAR_N> for F
+{
+    fn write_page(
+        &self,
+        offset: usize,
+        out: &mut PromptWrite<'_, CHAR_N>,
+    ) -> Result<(), ScrollerError> {
+        out.skip(offset);
+        self(out)
+    }
+}
+
+/// Lines given up front, e.g. a memo already split into chunks. Each line is followed by a
+/// line break, so in single-row mode it starts a page of its own.
+impl<const CHAR_N: usize> PromptSource<CHAR_N> for &[&str] {
+    fn write_page(
+        &self,
+        offset: usize,
+        out: &mut PromptWrite<'_, CHAR_N>,
+    ) -> Result<(), ScrollerError> {
+        // Offset of the line in the content, counting a line break before each but the first
+        let mut start = 0;
+        for (i, line) in self.iter().enumerate() {
+            let line_break = (i > 0) as usize;
+            let end = start + line_break + line.len();
+            if end > offset {
+                if start + line_break > offset {
+                    out.skip(offset.saturating_sub(start));
+                    out.write_char('\n')?;
+                } else {
+                    out.skip(offset - start - line_break);
+                }
+                out.write_str(line)?;
+            }
+            start = end;
+        }
+        Ok(())
+    }
+
+    /// The length is only a hint for a single line of ASCII, which can be paged by dividing;
+    /// line breaks and other chars have to be walked.
+    fn len_hint(&self) -> Option<usize> {
+        match self {
+            [] => Some(0),
+            [line] if line.is_ascii() && !line.contains('\n') => Some(line.len()),
+            _ => None,
+        }
     }
 }
 
-pub struct WriteScroller<
-    'a,
-    F: for<'b> Fn(&mut PromptWrite<'b, CHAR_N>) -> Result<(), ScrollerError>,
-    const CHAR_N: usize,
-> {
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub struct WriteScroller<'a, S: PromptSource<CHAR_N>, const CHAR_N: usize> {
     title: &'a str,
-    contents: F,
+    contents: S,
+    three_rows_more_icon: Icon<'a>,
+    first_line_as_subtitle: bool,
+    loop_options: PageLoopOptions,
+    monospace: bool,
+    risk: RiskLevel,
+    page_counter: PageCounter,
+    pinned_row: Option<&'a str>,
+    line_numbers: bool,
+    side_glyph: Option<&'a ledger_device_sdk::ui::bitmaps::Glyph<'a>>,
+    counter_digits: usize,
+    reverse: bool,
+    page_break_char: Option<char>,
+    break_interval: Option<usize>,
+    title_on_first_page_only: bool,
+    max_pages: Option<usize>,
+    known_size: Option<ContentSize>,
+    word_wrap: bool,
+    show_index: bool,
+    three_rows: bool,
+    line_width: usize,
+    right_aligned: bool,
 }
 
+#[cfg(target_os = "nanos")]
+pub const SCREEN_HEIGHT: usize = 32;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[cfg(not(target_os = "nanos"))]
+pub const SCREEN_HEIGHT: usize = 64;
+
+/// Vertical center of the navigation arrows drawn by the SDK, on which the icons shown in
+/// their place are centered too.
+#[cfg(target_os = "nanos")]
+const ARROW_CENTER_Y: i16 = 16;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[cfg(not(target_os = "nanos"))]
+const ARROW_CENTER_Y: i16 = 32;
+/// Horizontal position of the icons shown in place of the right arrow.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const RIGHT_ICON_X: i16 = 120;
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 const CHECK_ICON: Icon = Icon::from(&bitmaps::CHECK_GLYPH);
-const RIGHT_CHECK: Icon = CHECK_ICON.shift_h(120);
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const RIGHT_CHECK: Icon = CHECK_ICON
+    .set_x(RIGHT_ICON_X)
+    .set_y(ARROW_CENTER_Y - bitmaps::CHECK_GLYPH.height as i16 / 2);
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const RIGHT_WARNING: Icon = Icon::from(&bitmaps::WARNING_SMALL_GLYPH)
+    .set_x(RIGHT_ICON_X)
+    .set_y(ARROW_CENTER_Y - bitmaps::WARNING_SMALL_GLYPH.height as i16 / 2);
+
+/// How risky it is to confirm a prompt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RiskLevel {
+    Normal,
+    Elevated,
+}
+
+/// Reasons for a confirmation to be considered risky.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RiskFlags {
+    pub blind_signing: bool,
+    pub high_fee: bool,
+    pub unknown_contract: bool,
+}
+
+impl RiskFlags {
+    pub fn level(&self) -> RiskLevel {
+        if self.blind_signing || self.high_fee || self.unknown_contract {
+            RiskLevel::Elevated
+        } else {
+            RiskLevel::Normal
+        }
+    }
+}
 
+/// Downward chevron in the spot of the right arrow, for use as the "more pages" indicator in
+/// three-row mode.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub const DOWN_ICON: Icon = Icon::from(&bitmaps::DOWN_GLYPH)
+    .set_x(RIGHT_ICON_X)
+    .set_y(ARROW_CENTER_Y - bitmaps::DOWN_GLYPH.height as i16 / 2);
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 impl<
         'a,
         F: for<'b> Fn(&mut PromptWrite<'b, CHAR_N>) -> Result<(), ScrollerError>,
@@ -124,194 +719,2472 @@ impl<
     > WriteScroller<'a, F, CHAR_N>
 {
     pub fn new(title: &'a str, contents: F) -> Self {
-        WriteScroller { title, contents }
+        WriteScroller::from_source(title, contents)
     }
+}
 
-    fn get_length(&self) -> Result<usize, ScrollerError> {
-        let mut buffer = ArrayString::new();
-        let mut prompt_write = PromptWrite {
-            offset: 0,
-            buffer: &mut buffer,
-            total: 0,
-        };
-        (self.contents)(&mut prompt_write)?;
-        let length = prompt_write.total;
-        trace!("Prompt length: {}", length);
-        Ok(length)
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl<'a, const CHAR_N: usize> WriteScroller<'a, &'a [&'a str], CHAR_N> {
+    /// Like `new`, but showing `lines`, each starting a new page, without a closure writing
+    /// them.
+    pub fn from_lines(title: &'a str, lines: &'a [&'a str]) -> Self {
+        WriteScroller::from_source(title, lines)
+    }
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl<'a, S: PromptSource<CHAR_N>, const CHAR_N: usize> WriteScroller<'a, S, CHAR_N> {
+    /// Like `new`, but showing the content of `source`.
+    pub fn from_source(title: &'a str, source: S) -> Self {
+        WriteScroller {
+            title,
+            contents: source,
+            three_rows_more_icon: RIGHT_ARROW,
+            first_line_as_subtitle: false,
+            loop_options: PageLoopOptions::DEFAULT,
+            monospace: false,
+            risk: RiskLevel::Normal,
+            page_counter: PageCounter::Text,
+            pinned_row: None,
+            line_numbers: false,
+            side_glyph: None,
+            counter_digits: 0,
+            reverse: false,
+            page_break_char: Some('\n'),
+            break_interval: None,
+            title_on_first_page_only: false,
+            max_pages: None,
+            known_size: None,
+            word_wrap: false,
+            show_index: false,
+            three_rows: false,
+            line_width: CHAR_N,
+            right_aligned: false,
+        }
     }
 
-    pub fn ask(&self, show_index: bool) -> bool {
-        self.ask_err(show_index).unwrap_or(false)
+    /// Show the index of the page in the title when the scroller is shown with `run`.
+    pub fn with_show_index(self, show_index: bool) -> Self {
+        WriteScroller { show_index, ..self }
     }
 
-    pub fn ask_err(&self, show_index: bool) -> Result<bool, ScrollerError> {
-        let mut buttons = ButtonsState::new();
-        let page_count = (core::cmp::max(1, self.get_length()?) - 1) / CHAR_N + 1;
-        if page_count == 0 {
-            return Ok(true);
+    /// Show three rows of the content on each page when the scroller is shown with the `run`
+    /// methods, as `ask_three_rows` does, rather than one.
+    pub fn with_three_rows(self, three_rows: bool) -> Self {
+        WriteScroller { three_rows, ..self }
+    }
+
+    /// Reject the prompt once no button has been touched for `idle_ticks` ticks, e.g.
+    /// `30 * TICKS_PER_SECOND`.
+    pub fn with_timeout(mut self, idle_ticks: usize) -> Self {
+        self.loop_options.idle_timeout = Some(idle_ticks);
+        self
+    }
+
+    /// Use `icon` instead of the right arrow to indicate that more pages follow in three-row
+    /// mode, e.g. `DOWN_ICON`.
+    pub fn with_three_rows_more_icon(self, icon: Icon<'a>) -> Self {
+        WriteScroller {
+            three_rows_more_icon: icon,
+            ..self
         }
-        if page_count > 1000 {
-            trace!("Page count too large: {}", page_count);
-            panic!("Page count too large: {}", page_count);
+    }
+
+    /// In three-row mode, show the first line of the content, up to the first `\n`, in bold on
+    /// the first row of every page, below the pinned row if there is one, and page the rest of
+    /// the content through the other rows.
+    pub fn with_first_line_as_subtitle(self, first_line_as_subtitle: bool) -> Self {
+        WriteScroller {
+            first_line_as_subtitle,
+            ..self
         }
-        let mut cur_page = 0;
+    }
 
-        // A closure to draw common elements of the screen
-        // cur_page passed as parameter to prevent borrowing
-        let draw = |page: usize| -> Result<(), ScrollerError> {
-            clear_screen();
-            let offset = page * CHAR_N;
-            let mut buffer = ArrayString::new();
-            (self.contents)(&mut PromptWrite {
-                offset,
-                buffer: &mut buffer,
-                total: 0,
-            })?;
+    /// Choose what pressing both buttons does; the default is `BothButtonAction::Reject`.
+    pub fn with_both_buttons(mut self, both_buttons: BothButtonAction) -> Self {
+        self.loop_options.both_buttons = both_buttons;
+        self
+    }
 
-            if show_index {
-                let title_buffer = self.make_title_buffer(page, page_count);
-                let title_label: Label = From::from(title_buffer.as_str());
-                title_label.location(Location::Top).display();
-            } else {
-                let title_label: Label = From::from(self.title);
-                title_label.location(Location::Top).display();
-            };
-            let label: Label = From::from(buffer.as_str());
-            label.location(Location::Custom(15)).display();
-            trace!(
-                "Prompting with ({} of {}) {}: {}",
-                page,
-                page_count,
-                self.title,
-                buffer
-            );
-            if page > 0 {
-                LEFT_ARROW.instant_display();
-            }
-            if page + 1 < page_count {
-                RIGHT_ARROW.instant_display();
-            } else {
-                RIGHT_CHECK.instant_display();
-            }
-            Ok(())
-        };
+    /// On the last page, let a long press of the left button (held for `LONG_PRESS_TICKS`)
+    /// go back to the first page to review the content again.
+    pub fn with_review_again(mut self, review_again: bool) -> Self {
+        self.loop_options.review_again = review_again;
+        self
+    }
 
-        draw(cur_page)?;
+    /// Choose whether reaching the last page confirms on its own; the default is
+    /// `EndBehavior::RequirePress`.
+    pub fn with_end_behavior(mut self, end_behavior: EndBehavior) -> Self {
+        self.loop_options.end_behavior = end_behavior;
+        self
+    }
 
-        loop {
-            match get_event(&mut buttons) {
-                Some(ButtonEvent::LeftButtonPress) => {
-                    LEFT_S_ARROW.instant_display();
-                }
-                Some(ButtonEvent::RightButtonPress) => {
-                    RIGHT_S_ARROW.instant_display();
-                }
-                Some(ButtonEvent::LeftButtonRelease) => {
-                    if cur_page > 0 {
-                        cur_page -= 1;
-                    }
-                    // We need to draw anyway to clear button press arrow
-                    draw(cur_page)?;
-                }
-                Some(ButtonEvent::RightButtonRelease) => {
-                    if cur_page < page_count {
-                        cur_page += 1;
-                    }
-                    if cur_page == page_count {
-                        break Ok(true);
-                    }
-                    // We need to draw anyway to clear button press arrow
-                    draw(cur_page)?;
-                }
-                Some(ButtonEvent::BothButtonsRelease) => break Ok(false),
-                Some(_) | None => (),
-            }
-        }
+    /// After moving to another page, flash the small arrow pointing in the direction moved
+    /// until the next tick, to help keep track of the position in long content.
+    pub fn with_direction_hints(mut self, direction_hints: bool) -> Self {
+        self.loop_options.direction_hints = direction_hints;
+        self
     }
 
-    pub fn ask_three_rows(&self, show_index: bool) -> bool {
-        self.ask_three_rows_err(show_index).unwrap_or(false)
+    /// Let moving left from the first page leave the scroller, so that `run_decision` and
+    /// `run_with_intents` return `Decision::Back`; `run` and `run_at` count it as a rejection.
+    pub fn with_exit_on_left_at_start(mut self, exit_on_left_at_start: bool) -> Self {
+        self.loop_options.exit_on_left_at_start = exit_on_left_at_start;
+        self
     }
 
-    pub fn ask_three_rows_err(&self, show_index: bool) -> Result<bool, ScrollerError> {
-        let mut buttons = ButtonsState::new();
-        let content_len = core::cmp::max(1, self.get_length()?);
-        let page_count = (content_len - 1) / (CHAR_N * 3) + 1;
-        if page_count == 0 {
-            return Ok(true);
-        }
-        if page_count > 1000 {
-            trace!("Page count too large: {}", page_count);
-            panic!("Page count too large: {}", page_count);
-        }
-        let mut cur_page = 0;
+    /// Let a long press of the left button (held for `LONG_PRESS_TICKS`) go to the first
+    /// page, and one of the right button to the last page, to get through long content
+    /// quickly. The jump to the last page is left out with `with_require_scroll`, as it skips
+    /// the pages before it; there a long press moves one page like a short one.
+    pub fn with_long_press_jump(mut self, long_press_jump: bool) -> Self {
+        self.loop_options.long_press_jump = long_press_jump;
+        self
+    }
 
-        // A closure to draw common elements of the screen
+    /// While the left or the right button is held, move one page every `interval` ticks once
+    /// it has been held for `delay` ticks, to page through long content without pressing for
+    /// each page. Releasing the button then only stops the moves, and the last page reached
+    /// this way is only confirmed on release with `EndBehavior::AutoConfirm`. Holding both
+    /// buttons repeats too, as the first button pressed, so `delay` should not be too short.
+    pub fn with_auto_repeat(mut self, delay: usize, interval: usize) -> Self {
+        self.loop_options.auto_repeat = Some((delay, core::cmp::max(1, interval)));
+        self
+    }
+
+    /// Only offer to accept once the last page has been reached by moving right, so that every
+    /// page before it was shown. When the scroller starts on its last page, as a single page
+    /// does, moving right first marks it as seen, and the arrow is then replaced by the check.
+    pub fn with_require_scroll(mut self, require_scroll: bool) -> Self {
+        self.loop_options.require_scroll = require_scroll;
+        self
+    }
+
+    /// Treat releases of the left and right buttons less than `ticks` apart as both buttons,
+    /// for devices on which pressing both is not always detected. Single releases then take
+    /// effect after up to `ticks` idle events; 0, the default, turns this off.
+    pub fn with_both_buttons_window(mut self, ticks: usize) -> Self {
+        self.loop_options.both_buttons_window = ticks;
+        self
+    }
+
+    /// Draw the content in fixed-width cells so that columns of tabular data, such as hex or
+    /// numbers, line up between rows and pages.
+    pub fn with_monospace(self, monospace: bool) -> Self {
+        WriteScroller { monospace, ..self }
+    }
+
+    /// Mark the confirmation on the last page with a warning when `risk` is elevated, and show
+    /// the title in bold there.
+    pub fn with_risk(self, risk: RiskLevel) -> Self {
+        WriteScroller { risk, ..self }
+    }
+
+    /// Choose how the page index is shown when asking with `show_index`.
+    pub fn with_page_counter(self, page_counter: PageCounter) -> Self {
+        WriteScroller {
+            page_counter,
+            ..self
+        }
+    }
+
+    /// In three-row mode, show `pinned_row` on the first row of every page, e.g. the account a
+    /// paged address belongs to, and page the content through the other rows.
+    pub fn with_pinned_row(self, pinned_row: &'a str) -> Self {
+        WriteScroller {
+            pinned_row: Some(pinned_row),
+            ..self
+        }
+    }
+
+    /// In three-row mode, prefix each content row with its line number, counted across pages,
+    /// e.g. "1. ", "2. ". The numbers are padded to the same width, which is taken from the
+    /// characters available for content on each row.
+    pub fn with_line_numbers(self, line_numbers: bool) -> Self {
+        WriteScroller {
+            line_numbers,
+            ..self
+        }
+    }
+
+    /// Show `glyph`, e.g. a token logo, to the left of the content in single-row mode, and
+    /// center the content in the space that is left. The glyph can be built in or made at
+    /// runtime with `Glyph::new`.
+    pub fn with_side_glyph(self, glyph: &'a ledger_device_sdk::ui::bitmaps::Glyph<'a>) -> Self {
+        WriteScroller {
+            side_glyph: Some(glyph),
+            ..self
+        }
+    }
+
+    /// Pad both numbers of the " (x/y)" page counter to at least `counter_digits` digits, so that
+    /// the title doesn't shift as the page changes.
+    pub fn with_counter_digits(self, counter_digits: usize) -> Self {
+        WriteScroller {
+            counter_digits,
+            ..self
+        }
+    }
+
+    /// Show the pages last to first, e.g. for newest-first logs. Moving right goes towards the
+    /// first page of the content, and moving right past it accepts. The page index shown is the
+    /// position in this order.
+    pub fn with_reverse(self, reverse: bool) -> Self {
+        WriteScroller { reverse, ..self }
+    }
+
+    /// In single-row mode, start a new page after each `page_break_char` in the content instead
+    /// of after each `\n`. The delimiter itself is not shown, and two delimiters in a row give
+    /// an empty page. A part between delimiters that is longer than a page goes on over the
+    /// following pages, as the content does without delimiters.
+    pub fn with_page_break_char(self, page_break_char: char) -> Self {
+        WriteScroller {
+            page_break_char: Some(page_break_char),
+            ..self
+        }
+    }
+
+    /// Only end pages, or rows in three-row mode, between groups of `interval` chars, e.g. so
+    /// that a long hash is cut into groups of the same size rather than wherever the page is
+    /// full. With `with_word_wrap`, the groups are counted from the start of each word, and a
+    /// page may also end after whitespace. A group longer than a page is cut where it is full.
+    pub fn with_break_interval(self, interval: usize) -> Self {
+        WriteScroller {
+            break_interval: Some(core::cmp::max(1, interval)),
+            ..self
+        }
+    }
+
+    /// Only show the title on the first page of the content, e.g. for a single long field. In
+    /// three-row mode the later pages show a row of content in its place.
+    pub fn with_title_on_first_page_only(self, title_on_first_page_only: bool) -> Self {
+        WriteScroller {
+            title_on_first_page_only,
+            ..self
+        }
+    }
+
+    /// In single-row mode, show at most `max_pages` pages of the content, ending the last of
+    /// them with `format::ELLIPSIS` when there is more, e.g. to bound the review of one field.
+    pub fn with_max_pages(self, max_pages: usize) -> Self {
+        WriteScroller {
+            max_pages: Some(core::cmp::max(1, max_pages)),
+            ..self
+        }
+    }
+
+    /// In single-row mode, end pages after the last whitespace that fits instead of in the
+    /// middle of a word, unless the word is longer than a page.
+    pub fn with_word_wrap(self, word_wrap: bool) -> Self {
+        WriteScroller { word_wrap, ..self }
+    }
+
+    /// Show at most `width` chars on a row instead of `CHAR_N`, e.g. for a device or font on
+    /// which fewer fit. Pages and rows are then found for `width`, while `CHAR_N` stays the
+    /// size of the buffers they are written into and so the most that `width` can be.
+    pub fn with_line_width(self, width: usize) -> Self {
+        WriteScroller {
+            line_width: width.clamp(1, CHAR_N),
+            ..self
+        }
+    }
+
+    /// In single-row mode, align the last page of the content on the right of the screen, so
+    /// that amounts shown one after the other line up. Earlier pages are full, and are shown
+    /// as usual.
+    pub fn with_right_aligned(self, right_aligned: bool) -> Self {
+        WriteScroller {
+            right_aligned,
+            ..self
+        }
+    }
+
+    /// Take the content to be `len` bytes of ASCII without line breaks instead of writing it
+    /// out to find its size, e.g. when the length is known from a previous `run`. Pages are
+    /// then found by dividing the length. Content that has other chars or line breaks is
+    /// sized with `content_size` and given with `with_known_size`.
+    pub fn with_known_length(self, len: usize) -> Self {
+        self.with_known_size(ContentSize {
+            bytes: len,
+            chars: len,
+            newlines: 0,
+        })
+    }
+
+    /// Take the content to have `size`, as from `content_size` on a scroller of the same
+    /// content, instead of writing it out to find it.
+    pub fn with_known_size(self, size: ContentSize) -> Self {
+        WriteScroller {
+            known_size: Some(size),
+            ..self
+        }
+    }
+
+    /// Size of the content, found by writing it out once unless it is known, e.g. to give to
+    /// `with_known_size` when the same content is shown again.
+    pub fn content_size(&self) -> Result<ContentSize, ScrollerError> {
+        self.get_length()
+    }
+
+    /// Whether `ask` leaves out part of the content for `with_max_pages`.
+    pub fn is_truncated(&self) -> Result<bool, ScrollerError> {
+        Ok(self.single_row_layout()?.truncated)
+    }
+
+    /// Whether the title is shown on `page` of the content.
+    fn shows_title(&self, content_page: usize) -> bool {
+        !self.title_on_first_page_only || content_page == 0
+    }
+
+    /// Page of the content shown at position `page` of the scroller.
+    fn content_page(&self, page: usize, page_count: usize) -> usize {
+        if self.reverse {
+            page_count - 1 - page
+        } else {
+            page
+        }
+    }
+
+    fn display_title(&self, show_index: bool, page: usize, page_count: usize) {
+        let bold = self.risk == RiskLevel::Elevated && page + 1 == page_count;
+        if show_index && self.shows_page_dots(page_count) {
+            self.title.place(Location::Top, Layout::Centered, bold);
+            display_page_dots(page, page_count);
+        } else if show_index && self.page_counter == PageCounter::Glyphs && page_count > 1 {
+            let counter_end = display_glyph_counter(page, page_count);
+            place_title_beside(self.title, counter_end as usize + 1, bold);
+        } else if show_index && self.page_counter == PageCounter::Percent {
+            let title_buffer = make_percent_title_buffer(self.title, page, page_count);
+            title_buffer
+                .as_str()
+                .place(Location::Top, Layout::Centered, bold);
+        } else if show_index {
+            let title_buffer = self.make_title_buffer(page, page_count);
+            title_buffer
+                .as_str()
+                .place(Location::Top, Layout::Centered, bold);
+        } else {
+            self.title.place(Location::Top, Layout::Centered, bold);
+        }
+    }
+
+    /// Whether the page index is shown as dots for `page_count` pages.
+    fn shows_page_dots(&self, page_count: usize) -> bool {
+        self.page_counter == PageCounter::Dots && page_count > 1 && page_count <= MAX_PAGE_DOTS
+    }
+
+    fn display_arrows(&self, page: usize, page_count: usize, more_icon: &Icon) {
+        if page > 0 {
+            LEFT_ARROW.instant_display();
+        }
+        if page + 1 < page_count {
+            more_icon.instant_display();
+        } else if self.risk == RiskLevel::Elevated {
+            RIGHT_WARNING.instant_display();
+        } else {
+            RIGHT_CHECK.instant_display();
+        }
+    }
+
+    fn display_row(&self, text: &str, y: usize) {
+        if self.monospace {
+            place_monospace(text, y, monospace_cell_width());
+        } else {
+            let label: Label = From::from(text);
+            label.location(Location::Custom(y)).display();
+        }
+    }
+
+    /// Whether `content_page` is aligned on the right in single-row mode.
+    fn right_aligned_page(&self, content_page: usize, page_count: usize) -> bool {
+        self.right_aligned && !self.monospace && content_page + 1 == page_count
+    }
+
+    /// Size of the content. Offsets into the content count bytes, and pages hold a number of
+    /// chars and end at line breaks, so when the content is not ASCII or has line breaks, pages
+    /// are found by walking the content rather than by dividing its length.
+    fn get_length(&self) -> Result<ContentSize, ScrollerError> {
+        if let Some(size) = self.known_size {
+            return Ok(size);
+        }
+        match self.contents.len_hint() {
+            Some(len) => Ok(ContentSize {
+                bytes: len,
+                chars: len,
+                newlines: 0,
+            }),
+            None => content_size(&self.contents),
+        }
+    }
+
+    /// Write the content to `out`, which keeps the part of it from its offset on.
+    fn write_contents(&self, out: &mut PromptWrite<'_, CHAR_N>) -> Result<(), ScrollerError> {
+        let offset = core::mem::take(&mut out.offset);
+        self.contents.write_page(offset, out)
+    }
+
+    /// Length of the content before the first `\n`, if there is one.
+    fn first_line_length(&self, content_len: usize) -> Result<Option<usize>, ScrollerError> {
+        self.find_char('\n', 0, content_len)
+    }
+
+    /// Offset of the first `c` in the content at or after `from`, if there is one.
+    fn find_char(
+        &self,
+        c: char,
+        from: usize,
+        content_len: usize,
+    ) -> Result<Option<usize>, ScrollerError> {
+        let mut offset = from;
+        while offset < content_len {
+            let mut buffer: ArrayString<CHAR_N> = ArrayString::new();
+            self.write_contents(&mut PromptWrite {
+                offset,
+                buffer: &mut buffer,
+                total: 0,
+                chars: 0,
+                newlines: 0,
+                overflow: &mut [],
+                full: false,
+            })?;
+            if let Some(i) = buffer.find(c) {
+                return Ok(Some(offset + i));
+            }
+            if buffer.is_empty() {
+                break;
+            }
+            // Buffers end on a char, which the next one starts after
+            offset += buffer.len();
+        }
+        Ok(None)
+    }
+
+    /// Length of the page at the start of `text`, the content from the offset of the page on,
+    /// of a segment of the content with `rest` bytes left. Unless the content is `ascii`, the
+    /// page is cut back to the last char that fits, and then to where it may be wrapped.
+    fn page_len(&self, text: &[ArrayString<CHAR_N>; 2], rest: usize, ascii: bool) -> usize {
+        let len = core::cmp::min(self.line_width, rest);
+        let len = if ascii || len == rest {
+            len
+        } else {
+            fitted(&text[0], len)
+        };
+        if (self.word_wrap || self.break_interval.is_some()) && len < rest {
+            wrapped_len(text, len, self.word_wrap, self.break_interval)
+        } else {
+            len
+        }
+    }
+
+    /// Number of pages shown by `ask`, and the offset and length of the text on `page`, if
+    /// there is such a page. Pages end at each page break character, and otherwise after
+    /// `page_len` characters.
+    #[cfg(test)]
+    fn page_spans(&self, page: usize) -> Result<(usize, Option<(usize, usize)>), ScrollerError> {
+        let mut found = None;
+        let page_count =
+            self.walk_page_spans(self.get_length()?, (0, 0), |span_page, offset, text| {
+                if span_page == page {
+                    found = Some((offset, text.len()));
+                }
+                true
+            })?;
+        Ok((page_count, found))
+    }
+
+    /// Walk the pages shown by `ask`, as for `page_spans`, from `from`, the index and offset of
+    /// a page, calling `visit` with the index, offset and text of each until it returns false.
+    /// Returns the number of pages, or the pages up to where `visit` stopped.
+    fn walk_page_spans(
+        &self,
+        content_size: ContentSize,
+        from: (usize, usize),
+        mut visit: impl FnMut(usize, usize, &str) -> bool,
+    ) -> Result<usize, ScrollerError> {
+        let content_len = content_size.bytes;
+        let ascii = content_size.bytes == content_size.chars;
+        let (mut page_count, mut offset) = from;
+        loop {
+            // The content is written once per page, from its offset on, and the page break
+            // that ends its segment is looked for in what was written. A break that is not
+            // there is further off than a page.
+            let mut text = [ArrayString::<CHAR_N>::new(); 2];
+            let (buffer, overflow) = text.split_at_mut(1);
+            self.write_contents(&mut PromptWrite {
+                offset,
+                buffer: &mut buffer[0],
+                total: 0,
+                chars: 0,
+                newlines: 0,
+                overflow,
+                full: false,
+            })?;
+            let end = self.page_break_char.and_then(|c| {
+                let first = &text[0];
+                first
+                    .find(c)
+                    .or_else(|| text[1].find(c).map(|i| first.len() + i))
+                    .map(|i| (offset + i, c))
+            });
+            let segment_end = end.map_or(content_len, |(end, _)| end);
+            // An empty segment still takes a page
+            let len = self.page_len(&text, segment_end.saturating_sub(offset), ascii);
+            let page_text = &text[0][..floor_char_boundary(&text[0], len)];
+            let more = visit(page_count, offset, page_text);
+            page_count += 1;
+            if !more {
+                break;
+            }
+            offset += len;
+            if offset >= segment_end {
+                match end {
+                    Some((end, c)) => offset = end + c.len_utf8(),
+                    None => break,
+                }
+            }
+        }
+        Ok(page_count)
+    }
+
+    /// Whether every page shown by `ask` but the last holds a full row of chars, given the
+    /// `content_size`, so that pages can be found by dividing offsets.
+    fn uniform_pages(&self, content_size: ContentSize) -> bool {
+        let no_breaks = match self.page_break_char {
+            None => true,
+            Some('\n') => content_size.newlines == 0,
+            Some(_) => false,
+        };
+        no_breaks
+            && self.break_interval.is_none()
+            && !self.word_wrap
+            && content_size.bytes == content_size.chars
+    }
+
+    /// Layout of the content in single-row mode. Unless the pages can be found by dividing
+    /// its length, the content is written out once per page to find them.
+    fn single_row_layout(&self) -> Result<SingleRowLayout, ScrollerError> {
+        let content_size = self.get_length()?;
+        let uniform = self.uniform_pages(content_size);
+        let full_page_count = if uniform {
+            page_count(content_size.bytes, self.line_width, 1)
+        } else {
+            self.walk_page_spans(content_size, (0, 0), |_, _, _| true)?
+        };
+        let (page_count, truncated) = match self.max_pages {
+            Some(max_pages) if full_page_count > max_pages => (max_pages, true),
+            _ => (full_page_count, false),
+        };
+        Ok(SingleRowLayout {
+            content_size,
+            uniform,
+            page_count,
+            truncated,
+        })
+    }
+
+    /// Number of pages shown by `ask`.
+    fn single_row_page_count(&self) -> Result<usize, ScrollerError> {
+        Ok(self.single_row_layout()?.page_count)
+    }
+
+    /// Text shown on `page` by `ask`.
+    fn page_text(&self, page: usize) -> Result<ArrayString<CHAR_N>, ScrollerError> {
+        self.page_text_in(
+            &self.single_row_layout()?,
+            page,
+            &core::cell::Cell::new((0, 0)),
+        )
+    }
+
+    /// Text shown on `page` by `ask`, given the `layout` of the content. Unless pages are
+    /// found by dividing offsets, they are walked from `known`, the index and offset of the
+    /// page found last, if that is not after `page`, which is left in `known` in its place. So
+    /// redrawing a page writes the content once, and going on to the next page writes it twice.
+    fn page_text_in(
+        &self,
+        layout: &SingleRowLayout,
+        page: usize,
+        known: &core::cell::Cell<(usize, usize)>,
+    ) -> Result<ArrayString<CHAR_N>, ScrollerError> {
+        let mut buffer = ArrayString::new();
+        if layout.uniform {
+            self.write_contents(&mut PromptWrite {
+                offset: page * self.line_width,
+                buffer: &mut buffer,
+                total: 0,
+                chars: 0,
+                newlines: 0,
+                overflow: &mut [],
+                full: false,
+            })?;
+            buffer.truncate(floor_char_boundary(&buffer, self.line_width));
+        } else {
+            let from = match known.get() {
+                (at, offset) if at <= page => (at, offset),
+                _ => (0, 0),
+            };
+            self.walk_page_spans(layout.content_size, from, |span_page, offset, text| {
+                if span_page < page {
+                    return true;
+                }
+                known.set((span_page, offset));
+                let _ = buffer.try_push_str(text);
+                false
+            })?;
+        }
+        if self.word_wrap {
+            // Whitespace the pages were broken at is not shown
+            let mut trimmed = ArrayString::new();
+            let _ = trimmed.try_push_str(buffer.trim());
+            buffer = trimmed;
+        }
+        if Some(page + 1) == self.max_pages && layout.truncated {
+            let marker_len = format::ELLIPSIS.len();
+            buffer.truncate(floor_char_boundary(
+                &buffer,
+                self.line_width.saturating_sub(marker_len),
+            ));
+            let _ = buffer.try_push_str(format::ELLIPSIS);
+        }
+        Ok(buffer)
+    }
+
+    /// The text of each page shown by `ask`, in order, e.g. to log exactly what was on each
+    /// screen. The content is written once per page, as when it is displayed.
+    pub fn pages(&self) -> Result<Pages<'_, 'a, S, CHAR_N>, ScrollerError> {
+        Ok(Pages {
+            scroller: self,
+            layout: self.single_row_layout()?,
+            page: 0,
+            known: core::cell::Cell::new((0, 0)),
+        })
+    }
+
+    /// Render `page` as `ask` shows it into `framebuffer`, with text sized by `metrics`. The
+    /// navigation arrows, which are drawn by the SDK, are left out.
+    #[cfg(feature = "host-stub")]
+    pub fn render_page(
+        &self,
+        show_index: bool,
+        page: usize,
+        framebuffer: &mut snapshot::Framebuffer,
+        metrics: &impl FontMetrics,
+    ) -> Result<(), ScrollerError> {
+        let layout = self.single_row_layout()?;
+        let page_count = layout.page_count;
+        let content_page = self.content_page(page, page_count);
+        let text = self.page_text_in(&layout, content_page, &core::cell::Cell::new((0, 0)))?;
+        let dots = show_index && self.shows_page_dots(page_count);
+        let title = match (show_index, self.page_counter) {
+            (true, PageCounter::Text) => self.make_title_buffer(page, page_count),
+            (true, PageCounter::Dots) if !dots => self.make_title_buffer(page, page_count),
+            (true, PageCounter::Percent) => make_percent_title_buffer(self.title, page, page_count),
+            _ => {
+                let mut title: ArrayString<16> = ArrayString::new();
+                let _ = mk_prompt_write(&mut title).write_str(self.title);
+                title
+            }
+        };
+        let bold = self.risk == RiskLevel::Elevated && page + 1 == page_count;
+        if self.shows_title(content_page) {
+            framebuffer.draw_text(title.as_str(), 0, Layout::Centered, bold, metrics);
+        }
+        if dots {
+            for dot in 0..page_count {
+                let x = page_dot_x(dot, page_count);
+                framebuffer.draw_glyph(page_dot_glyph(dot, page), x, PAGE_DOTS_Y);
+            }
+        }
+        match self.side_glyph {
+            Some(glyph) => {
+                let glyph_y =
+                    (SINGLE_ROW_Y + TEXT_ROW_HEIGHT / 2).saturating_sub(glyph.height as usize / 2);
+                framebuffer.draw_glyph(glyph, SIDE_GLYPH_X as usize, glyph_y);
+                let space = SCREEN_WIDTH - SIDE_GLYPH_AREA_WIDTH;
+                let width = metrics.text_width(text.as_str(), false);
+                let x = SIDE_GLYPH_AREA_WIDTH + space.saturating_sub(width) / 2;
+                framebuffer.draw_text(
+                    text.as_str(),
+                    SINGLE_ROW_Y,
+                    Layout::Custom(x),
+                    false,
+                    metrics,
+                );
+            }
+            None => framebuffer.draw_text(
+                text.as_str(),
+                SINGLE_ROW_Y,
+                if self.right_aligned_page(content_page, page_count) {
+                    Layout::RightAligned
+                } else {
+                    Layout::Centered
+                },
+                false,
+                metrics,
+            ),
+        }
+        Ok(())
+    }
+
+    /// Index of the page on which `ask` shows the character at `offset` into the content, e.g.
+    /// to start `run_at` there. Pages are found as for drawing them, so line breaks, word wrap
+    /// and non-ASCII content are taken into account, as are `with_max_pages`, which keeps
+    /// later offsets on the last page, and `with_reverse`.
+    pub fn page_for_offset(&self, offset: usize) -> Result<usize, ScrollerError> {
+        let layout = self.single_row_layout()?;
+        let page_count = layout.page_count;
+        let content_page = if layout.uniform {
+            offset / self.line_width
+        } else {
+            // The last page starting at or before the offset
+            let mut found = 0;
+            self.walk_page_spans(layout.content_size, (0, 0), |page, start, _| {
+                if start <= offset {
+                    found = page;
+                }
+                true
+            })?;
+            found
+        };
+        let content_page = core::cmp::min(content_page, page_count.saturating_sub(1));
+        Ok(self.content_page(content_page, page_count))
+    }
+
+    /// Index of the page on which `ask_three_rows` shows the character at `offset` into the
+    /// content, as for `page_for_offset`. Offsets in the subtitle are on the first page.
+    pub fn page_for_offset_three_rows(&self, offset: usize) -> Result<usize, ScrollerError> {
+        let layout = self.three_rows_layout()?;
+        let line = if offset < layout.body_start {
+            0
+        } else if layout.uniform_rows {
+            (offset - layout.body_start) / layout.row_chars
+        } else {
+            // The last row starting at or before the offset
+            let mut line = 0;
+            let mut start = layout.body_start;
+            let mut rows = [ArrayString::<CHAR_N>::new(); THREE_ROWS_Y.len() + 1];
+            'walk: loop {
+                let (count, ends) =
+                    self.write_rows(start, layout.row_chars, layout.total_len, &mut rows)?;
+                for &next in &ends[..count] {
+                    if next > offset || next >= layout.total_len || next == start {
+                        break 'walk;
+                    }
+                    start = next;
+                    line += 1;
+                }
+                rows.iter_mut().for_each(ArrayString::clear);
+            }
+            line
+        };
+        let content_page = core::cmp::min(layout.page_of_line(line), layout.page_count - 1);
+        Ok(self.content_page(content_page, layout.page_count))
+    }
+
+    /// Show the scroller as configured, with one or three rows per page as set with
+    /// `with_three_rows`, and wait for the user to accept or reject it. Together with the other
+    /// `run` methods, this covers the `ask` methods, which take the same options as arguments.
+    pub fn run(&self) -> PromptOutcome {
+        self.run_at(&mut 0)
+    }
+
+    /// Like `run`, but starting on page `*cursor` and leaving the last page shown in `*cursor`,
+    /// so that the position is kept when running again.
+    pub fn run_at(&self, cursor: &mut usize) -> PromptOutcome {
+        let mut buttons = ButtonsState::new();
+        let result = self.run_with_intents(cursor, button_intents(&mut buttons));
+        PromptOutcome::from(result.map(|decision| decision == Decision::Accept))
+    }
+
+    /// Like `run`, but returning the decision, which is `Decision::Back` when the user leaves
+    /// with `with_exit_on_left_at_start`, or the error if the prompt could not be shown.
+    pub fn run_decision(&self) -> Result<Decision, ScrollerError> {
+        let mut buttons = ButtonsState::new();
+        self.run_with_intents(&mut 0, button_intents(&mut buttons))
+    }
+
+    /// Like `run_decision`, starting on page `*cursor` as `run_at` does, but driven by
+    /// navigation intents from `next_intent` instead of the buttons, e.g. from a `JogEvent`,
+    /// with `None` for a tick.
+    pub fn run_with_intents(
+        &self,
+        cursor: &mut usize,
+        next_intent: impl FnMut() -> Option<NavIntent>,
+    ) -> Result<Decision, ScrollerError> {
+        if self.three_rows {
+            self.ask_three_rows_err_from(self.show_index, cursor, next_intent, self.loop_options)
+        } else {
+            self.ask_err_from(self.show_index, cursor, next_intent, self.loop_options)
+        }
+    }
+
+    /// Like `run_with_intents`, but taking button events from `next_event` instead of the SDK,
+    /// with `None` for a tick, e.g. from an app's own event loop.
+    pub fn run_with_events(
+        &self,
+        cursor: &mut usize,
+        next_event: impl FnMut() -> Option<ButtonEvent>,
+    ) -> Result<Decision, ScrollerError> {
+        self.run_with_intents(cursor, event_intents(next_event))
+    }
+
+    /// Show the scroller with one row per page, with the page index in the title if
+    /// `show_index` is set, taking intents from the buttons.
+    fn ask_buttons(
+        &self,
+        show_index: bool,
+        cursor: &mut usize,
+        options: PageLoopOptions,
+    ) -> Result<Decision, ScrollerError> {
+        let mut buttons = ButtonsState::new();
+        self.ask_err_from(show_index, cursor, button_intents(&mut buttons), options)
+    }
+
+    #[deprecated(note = "use WriteScroller::with_show_index(..).run()")]
+    pub fn ask(&self, show_index: bool) -> bool {
+        matches!(
+            self.ask_buttons(show_index, &mut 0, self.loop_options),
+            Ok(Decision::Accept)
+        )
+    }
+
+    #[deprecated(note = "use WriteScroller::with_show_index(..).run().result()")]
+    pub fn ask_err(&self, show_index: bool) -> Result<bool, ScrollerError> {
+        self.ask_buttons(show_index, &mut 0, self.loop_options)
+            .map(|decision| decision == Decision::Accept)
+    }
+
+    /// Like `ask`, but starting on page `*cursor` and leaving the last page shown in `*cursor`,
+    /// so that the position is kept when asking again.
+    #[deprecated(note = "use WriteScroller::with_show_index(..).run_at(cursor)")]
+    pub fn ask_at(&self, show_index: bool, cursor: &mut usize) -> bool {
+        matches!(
+            self.ask_buttons(show_index, cursor, self.loop_options),
+            Ok(Decision::Accept)
+        )
+    }
+
+    #[deprecated(note = "use WriteScroller::with_show_index(..).run_at(cursor).result()")]
+    pub fn ask_err_at(&self, show_index: bool, cursor: &mut usize) -> Result<bool, ScrollerError> {
+        self.ask_buttons(show_index, cursor, self.loop_options)
+            .map(|decision| decision == Decision::Accept)
+    }
+
+    /// Like `ask_err`, but returning `Decision::Back` when the user leaves with
+    /// `with_exit_on_left_at_start`.
+    #[deprecated(note = "use WriteScroller::with_show_index(..).run_decision()")]
+    pub fn ask_decision(&self, show_index: bool) -> Result<Decision, ScrollerError> {
+        self.ask_buttons(show_index, &mut 0, self.loop_options)
+    }
+
+    /// Like `ask_err`, but rejecting the prompt once no button has been touched for
+    /// `idle_ticks` ticks, e.g. `30 * TICKS_PER_SECOND`, so that a device left alone does not
+    /// wait forever.
+    #[deprecated(note = "use WriteScroller::with_timeout(..).run()")]
+    pub fn ask_with_timeout(
+        &self,
+        show_index: bool,
+        idle_ticks: usize,
+    ) -> Result<bool, ScrollerError> {
+        let options = PageLoopOptions {
+            idle_timeout: Some(idle_ticks),
+            ..self.loop_options
+        };
+        self.ask_buttons(show_index, &mut 0, options)
+            .map(|decision| decision == Decision::Accept)
+    }
+
+    /// Like `ask_err`, but driven by navigation intents from `next_intent` instead of the
+    /// buttons, e.g. from a `JogEvent`.
+    #[deprecated(note = "use WriteScroller::with_show_index(..).run_with_intents(..)")]
+    pub fn ask_err_with_intents(
+        &self,
+        show_index: bool,
+        next_intent: impl FnMut() -> Option<NavIntent>,
+    ) -> Result<bool, ScrollerError> {
+        self.ask_err_from(show_index, &mut 0, next_intent, self.loop_options)
+            .map(|decision| decision == Decision::Accept)
+    }
+
+    /// Like `ask_err`, but taking button events from `next_event` instead of the SDK, with
+    /// `None` for a tick, e.g. from an app's own event loop.
+    #[deprecated(note = "use WriteScroller::with_show_index(..).run_with_events(..)")]
+    pub fn ask_err_with_events(
+        &self,
+        show_index: bool,
+        next_event: impl FnMut() -> Option<ButtonEvent>,
+    ) -> Result<bool, ScrollerError> {
+        self.ask_err_from(
+            show_index,
+            &mut 0,
+            event_intents(next_event),
+            self.loop_options,
+        )
+        .map(|decision| decision == Decision::Accept)
+    }
+
+    fn ask_err_from(
+        &self,
+        show_index: bool,
+        cur_page: &mut usize,
+        next_intent: impl FnMut() -> Option<NavIntent>,
+        options: PageLoopOptions,
+    ) -> Result<Decision, ScrollerError> {
+        let layout = self.single_row_layout()?;
+        let page_count = layout.page_count;
+        if page_count == 0 {
+            return Ok(Decision::Accept);
+        }
+        if page_count > MAX_PAGE_COUNT {
+            trace!("Page count too large: {}", page_count);
+            return Err(ScrollerError::TooManyPages);
+        }
+
+        // The page found last and its offset, from which the next page is found without
+        // walking the content from the start
+        let known_page = core::cell::Cell::new((0, 0));
+        // A closure to draw common elements of the screen
         // cur_page passed as parameter to prevent borrowing
         let draw = |page: usize| -> Result<(), ScrollerError> {
             clear_screen();
-            if show_index {
-                let title_buffer = self.make_title_buffer(page, page_count);
-                let title_label: Label = From::from(title_buffer.as_str());
-                title_label.location(Location::Top).display();
+            let content_page = self.content_page(page, page_count);
+            let buffer = self.page_text_in(&layout, content_page, &known_page)?;
+
+            if self.shows_title(content_page) {
+                self.display_title(show_index, page, page_count);
+            }
+            let right_aligned = self.right_aligned_page(content_page, page_count);
+            match self.side_glyph {
+                Some(glyph) => display_beside_glyph(glyph, buffer.as_str(), SINGLE_ROW_Y),
+                None if right_aligned => {
+                    let text = buffer.as_str();
+                    text.place(Location::Custom(SINGLE_ROW_Y), Layout::RightAligned, false)
+                }
+                None => self.display_row(buffer.as_str(), SINGLE_ROW_Y),
+            }
+            trace!(
+                "Prompting with ({} of {}) {}: {}",
+                page,
+                page_count,
+                self.title,
+                buffer
+            );
+            self.display_arrows(page, page_count, &RIGHT_ARROW);
+            Ok(())
+        };
+
+        page_loop_intents(page_count, cur_page, draw, next_intent, options)
+    }
+
+    /// Show the scroller with three rows per page, as `ask_buttons` does with one.
+    fn ask_three_rows_buttons(
+        &self,
+        show_index: bool,
+        cursor: &mut usize,
+        options: PageLoopOptions,
+    ) -> Result<Decision, ScrollerError> {
+        let mut buttons = ButtonsState::new();
+        self.ask_three_rows_err_from(show_index, cursor, button_intents(&mut buttons), options)
+    }
+
+    /// Like `ask`, but showing three rows of the content on each page. Each `\n` in the
+    /// content starts a new row, and a line longer than a row goes on over the following rows.
+    #[deprecated(note = "use WriteScroller::with_three_rows(true).run()")]
+    pub fn ask_three_rows(&self, show_index: bool) -> bool {
+        matches!(
+            self.ask_three_rows_buttons(show_index, &mut 0, self.loop_options),
+            Ok(Decision::Accept)
+        )
+    }
+
+    #[deprecated(note = "use WriteScroller::with_three_rows(true).run().result()")]
+    pub fn ask_three_rows_err(&self, show_index: bool) -> Result<bool, ScrollerError> {
+        self.ask_three_rows_buttons(show_index, &mut 0, self.loop_options)
+            .map(|decision| decision == Decision::Accept)
+    }
+
+    /// Like `ask_three_rows`, but starting on page `*cursor` and leaving the last page shown in
+    /// `*cursor`.
+    #[deprecated(note = "use WriteScroller::with_three_rows(true).run_at(cursor)")]
+    pub fn ask_three_rows_at(&self, show_index: bool, cursor: &mut usize) -> bool {
+        matches!(
+            self.ask_three_rows_buttons(show_index, cursor, self.loop_options),
+            Ok(Decision::Accept)
+        )
+    }
+
+    #[deprecated(note = "use WriteScroller::with_three_rows(true).run_at(cursor).result()")]
+    pub fn ask_three_rows_err_at(
+        &self,
+        show_index: bool,
+        cursor: &mut usize,
+    ) -> Result<bool, ScrollerError> {
+        self.ask_three_rows_buttons(show_index, cursor, self.loop_options)
+            .map(|decision| decision == Decision::Accept)
+    }
+
+    /// Like `ask_three_rows_err`, but returning `Decision::Back` when the user leaves with
+    /// `with_exit_on_left_at_start`.
+    #[deprecated(note = "use WriteScroller::with_three_rows(true).run_decision()")]
+    pub fn ask_three_rows_decision(&self, show_index: bool) -> Result<Decision, ScrollerError> {
+        self.ask_three_rows_buttons(show_index, &mut 0, self.loop_options)
+    }
+
+    /// Like `ask_three_rows_err`, but rejecting the prompt once no button has been touched for
+    /// `idle_ticks` ticks.
+    #[deprecated(note = "use WriteScroller::with_three_rows(true).with_timeout(..).run()")]
+    pub fn ask_three_rows_with_timeout(
+        &self,
+        show_index: bool,
+        idle_ticks: usize,
+    ) -> Result<bool, ScrollerError> {
+        let options = PageLoopOptions {
+            idle_timeout: Some(idle_ticks),
+            ..self.loop_options
+        };
+        self.ask_three_rows_buttons(show_index, &mut 0, options)
+            .map(|decision| decision == Decision::Accept)
+    }
+
+    /// Like `ask_three_rows_err`, but driven by navigation intents from `next_intent`.
+    #[deprecated(note = "use WriteScroller::with_three_rows(true).run_with_intents(..)")]
+    pub fn ask_three_rows_err_with_intents(
+        &self,
+        show_index: bool,
+        next_intent: impl FnMut() -> Option<NavIntent>,
+    ) -> Result<bool, ScrollerError> {
+        self.ask_three_rows_err_from(show_index, &mut 0, next_intent, self.loop_options)
+            .map(|decision| decision == Decision::Accept)
+    }
+
+    /// Like `ask_three_rows_err`, but taking button events from `next_event`, as for
+    /// `ask_err_with_events`.
+    #[deprecated(note = "use WriteScroller::with_three_rows(true).run_with_events(..)")]
+    pub fn ask_three_rows_err_with_events(
+        &self,
+        show_index: bool,
+        next_event: impl FnMut() -> Option<ButtonEvent>,
+    ) -> Result<bool, ScrollerError> {
+        let intents = event_intents(next_event);
+        self.ask_three_rows_err_from(show_index, &mut 0, intents, self.loop_options)
+            .map(|decision| decision == Decision::Accept)
+    }
+
+    fn three_rows_layout(&self) -> Result<ThreeRowsLayout, ScrollerError> {
+        let content_size = self.get_length()?;
+        let total_len = content_size.bytes;
+        let subtitle_len = if self.first_line_as_subtitle {
+            self.first_line_length(total_len)?
+        } else {
+            None
+        };
+        // Rows that stay the same on every page: the pinned row, then the subtitle
+        let subtitle_row = self.pinned_row.is_some() as usize;
+        let fixed_rows = subtitle_row + subtitle_len.is_some() as usize;
+        let body_start = subtitle_len.map_or(0, |len| len + 1);
+        let body_rows = &THREE_ROWS_Y[fixed_rows..];
+        let content_len = core::cmp::max(1, total_len - body_start);
+        let (number_width, row_chars) = if self.line_numbers {
+            line_number_budget(content_len, self.line_width)
+        } else {
+            (0, self.line_width)
+        };
+        let body_newlines = content_size
+            .newlines
+            .saturating_sub(subtitle_len.is_some() as usize);
+        let uniform_rows = content_size.bytes == content_size.chars
+            && body_newlines == 0
+            && self.break_interval.is_none();
+        let row_count = if uniform_rows {
+            page_count(content_len, row_chars, 1)
+        } else {
+            let mut row_count = 0;
+            let mut offset = body_start;
+            let mut rows = [ArrayString::<CHAR_N>::new(); THREE_ROWS_Y.len() + 1];
+            loop {
+                let (count, ends) = self.write_rows(offset, row_chars, total_len, &mut rows)?;
+                row_count += count;
+                let next = ends[count - 1];
+                if next >= total_len || next == offset {
+                    break;
+                }
+                offset = next;
+                rows.iter_mut().for_each(ArrayString::clear);
+            }
+            row_count
+        };
+        Ok(ThreeRowsLayout {
+            subtitle_len,
+            subtitle_row,
+            body_start,
+            body_rows,
+            total_len,
+            number_width,
+            row_chars,
+            uniform_rows,
+            title_on_first_page_only: self.title_on_first_page_only,
+            page_count: if self.title_on_first_page_only && row_count > body_rows.len() {
+                page_count(row_count - body_rows.len(), 1, body_rows.len() + 1) + 1
             } else {
-                let title_label: Label = From::from(self.title);
-                title_label.location(Location::Top).display();
-            };
-            {
-                let offset = (3 * page) * CHAR_N;
-                let mut buffer = ArrayString::new();
-                (self.contents)(&mut PromptWrite {
-                    offset,
-                    buffer: &mut buffer,
-                    total: 0,
-                })?;
-                let label: Label = From::from(buffer.as_str());
-                label.location(Location::Custom(16)).display();
-                trace!(
-                    "Prompting row 1 ({} of {}) {}: {}",
-                    page,
-                    page_count,
-                    self.title,
-                    buffer
-                );
+                page_count(row_count, 1, body_rows.len())
+            },
+        })
+    }
+
+    /// Offset of row `line` of the body in three-row mode, found by walking the rows before it
+    /// unless they all hold `row_chars` chars. The walk starts from `known`, a row and its
+    /// offset, if that row is not after `line`, and otherwise from the first row.
+    fn row_offset(
+        &self,
+        layout: &ThreeRowsLayout,
+        line: usize,
+        known: (usize, usize),
+    ) -> Result<usize, ScrollerError> {
+        if layout.uniform_rows {
+            return Ok(layout.body_start + line * layout.row_chars);
+        }
+        let (mut at, mut offset) = if known.0 <= line {
+            known
+        } else {
+            (0, layout.body_start)
+        };
+        let mut rows = [ArrayString::<CHAR_N>::new(); THREE_ROWS_Y.len() + 1];
+        while at < line && offset < layout.total_len {
+            let (count, ends) =
+                self.write_rows(offset, layout.row_chars, layout.total_len, &mut rows)?;
+            let skipped = core::cmp::min(count, line - at);
+            if ends[skipped - 1] == offset {
+                break;
+            }
+            at += skipped;
+            offset = ends[skipped - 1];
+            rows.iter_mut().for_each(ArrayString::clear);
+        }
+        Ok(offset)
+    }
+
+    /// Write the rows of the body in three-row mode from `offset` on into `rows`, after what
+    /// they already hold, in one pass over the content. A row holds at most `limit` bytes, or
+    /// its first char if not even that fits, and ends early at a `\n`, which the next row
+    /// starts after, or between groups of chars of `with_break_interval` when the next group
+    /// does not fit. Rows from `total_len` on are left empty, unless it is the first.
+    ///
+    /// Returns the number of rows written and the offset each of them ends at.
+    fn write_rows(
+        &self,
+        offset: usize,
+        limit: usize,
+        total_len: usize,
+        rows: &mut [ArrayString<CHAR_N>],
+    ) -> Result<(usize, [usize; THREE_ROWS_Y.len() + 1]), ScrollerError> {
+        // Each row takes at most one buffer of content, and buffers may end a few bytes short
+        // to keep their last char whole, so there are twice as many as rows
+        let mut text = [ArrayString::<CHAR_N>::new(); 2 * (THREE_ROWS_Y.len() + 1)];
+        let (buffer, overflow) = text.split_at_mut(1);
+        self.write_contents(&mut PromptWrite {
+            offset,
+            buffer: &mut buffer[0],
+            total: 0,
+            chars: 0,
+            newlines: 0,
+            overflow,
+            full: false,
+        })?;
+        let mut chars = text.iter().flat_map(|buffer| buffer.chars()).peekable();
+        let mut ends = [offset; THREE_ROWS_Y.len() + 1];
+        let mut offset = offset;
+        let mut count = 0;
+        for (row, (buffer, end)) in rows.iter_mut().zip(&mut ends).enumerate() {
+            if row > 0 && offset >= total_len {
+                break;
+            }
+            let mut len = 0;
+            let mut row_chars = 0;
+            while let Some(&c) = chars.peek() {
+                if len > 0 && len + c.len_utf8() > limit {
+                    break;
+                }
+                // Rows only end between groups of chars of `break_interval`
+                if let Some(interval) = self.break_interval {
+                    if row_chars > 0 && row_chars % interval == 0 {
+                        let group: usize = chars
+                            .clone()
+                            .take(interval)
+                            .take_while(|&c| c != '\n')
+                            .map(char::len_utf8)
+                            .sum();
+                        if len + group > limit {
+                            break;
+                        }
+                    }
+                }
+                chars.next();
+                if c == '\n' {
+                    offset += 1;
+                    break;
+                }
+                // A row that does not fit in the buffer, after a line number, is cut short
+                let _ = buffer.try_push(c);
+                len += c.len_utf8();
+                row_chars += 1;
+            }
+            offset += len;
+            *end = offset;
+            count += 1;
+        }
+        Ok((count, ends))
+    }
+
+    /// Number of pages `ask` shows, without showing them, e.g. to tell the user how long
+    /// the review will take.
+    pub fn page_count(&self) -> Result<usize, ScrollerError> {
+        self.single_row_page_count()
+    }
+
+    /// Number of pages `ask_three_rows` shows, without showing them.
+    pub fn page_count_three_rows(&self) -> Result<usize, ScrollerError> {
+        Ok(self.three_rows_layout()?.page_count)
+    }
+
+    fn ask_three_rows_err_from(
+        &self,
+        show_index: bool,
+        cur_page: &mut usize,
+        next_intent: impl FnMut() -> Option<NavIntent>,
+        options: PageLoopOptions,
+    ) -> Result<Decision, ScrollerError> {
+        let layout = self.three_rows_layout()?;
+        let ThreeRowsLayout {
+            subtitle_len,
+            subtitle_row,
+            total_len,
+            body_rows,
+            number_width,
+            row_chars,
+            uniform_rows,
+            page_count,
+            ..
+        } = layout;
+        if page_count == 0 {
+            return Ok(Decision::Accept);
+        }
+        if page_count > MAX_PAGE_COUNT {
+            trace!("Page count too large: {}", page_count);
+            return Err(ScrollerError::TooManyPages);
+        }
+
+        // The row after the last page drawn and its offset, from which the rows of the next
+        // page are found without walking the content from the start
+        let next_row = core::cell::Cell::new((0, layout.body_start));
+        // A closure to draw common elements of the screen
+        // cur_page passed as parameter to prevent borrowing
+        let draw = |page: usize| -> Result<(), ScrollerError> {
+            clear_screen();
+            let content_page = self.content_page(page, page_count);
+            if self.shows_title(content_page) {
+                self.display_title(show_index, page, page_count);
+            }
+            if let Some(pinned_row) = self.pinned_row {
+                pinned_row.place(Location::Custom(THREE_ROWS_Y[0]), Layout::Centered, false);
             }
-            if content_len > ((3 * page) + 1) * CHAR_N {
-                let offset = ((3 * page) + 1) * CHAR_N;
-                let mut buffer = ArrayString::new();
-                (self.contents)(&mut PromptWrite {
-                    offset,
+            if let Some(len) = subtitle_len {
+                let mut buffer: ArrayString<CHAR_N> = ArrayString::new();
+                self.write_contents(&mut PromptWrite {
+                    offset: 0,
                     buffer: &mut buffer,
                     total: 0,
+                    chars: 0,
+                    newlines: 0,
+                    overflow: &mut [],
+                    full: false,
                 })?;
-                let label: Label = From::from(buffer.as_str());
-                label.location(Location::Custom(31)).display();
-                trace!(
-                    "Prompting row 2 ({} of {}) {}: {}",
-                    page,
-                    page_count,
-                    self.title,
-                    buffer
+                buffer.truncate(floor_char_boundary(&buffer, len));
+                buffer.as_str().place(
+                    Location::Custom(THREE_ROWS_Y[subtitle_row]),
+                    Layout::Centered,
+                    true,
                 );
             }
-            if content_len > ((3 * page) + 2) * CHAR_N {
-                let offset = ((3 * page) + 2) * CHAR_N;
-                let mut buffer = ArrayString::new();
-                (self.contents)(&mut PromptWrite {
-                    offset,
-                    buffer: &mut buffer,
-                    total: 0,
-                })?;
-                let label: Label = From::from(buffer.as_str());
-                label.location(Location::Custom(46)).display();
+            let first_line = layout.first_line(content_page);
+            let title_row = if self.shows_title(content_page) {
+                None
+            } else {
+                Some(&TITLE_ROW_Y)
+            };
+            let row_ys = title_row.into_iter().chain(body_rows);
+            // The rows of the page are written in one pass over the content, each row going on
+            // where the one before it is full
+            let mut buffers = [ArrayString::<CHAR_N>::new(); THREE_ROWS_Y.len() + 1];
+            let buffers = &mut buffers[..row_ys.clone().count()];
+            let mut prefix_lens = [0; THREE_ROWS_Y.len() + 1];
+            if self.line_numbers {
+                for (row, buffer) in buffers.iter_mut().enumerate() {
+                    write!(
+                        mk_prompt_write(buffer),
+                        "{:>width$}. ",
+                        first_line + row + 1,
+                        width = number_width
+                    )?;
+                    prefix_lens[row] = buffer.len();
+                }
+            }
+            let offset = self.row_offset(&layout, first_line, next_row.get())?;
+            // Full buffers are full rows unless the rows are narrower. Otherwise rows end at
+            // line breaks or before the end of the buffer, and are split from what was written.
+            let shown = if uniform_rows && self.line_width == CHAR_N {
+                if let Some((buffer, overflow)) = buffers.split_first_mut() {
+                    self.write_contents(&mut PromptWrite {
+                        offset,
+                        buffer,
+                        total: 0,
+                        chars: 0,
+                        newlines: 0,
+                        overflow,
+                        full: false,
+                    })?;
+                }
+                let mut end = offset;
+                let mut shown = 0;
+                for (row, buffer) in buffers.iter().enumerate() {
+                    if row > 0 && end >= total_len {
+                        break;
+                    }
+                    end += buffer.len() - prefix_lens[row];
+                    shown += 1;
+                }
+                shown
+            } else {
+                let (count, ends) = self.write_rows(offset, row_chars, total_len, buffers)?;
+                next_row.set((first_line + count, ends[count - 1]));
+                count
+            };
+            for (row, (buffer, y)) in buffers[..shown].iter().zip(row_ys).enumerate() {
+                self.display_row(buffer.as_str(), *y);
                 trace!(
-                    "Prompting row 3 ({} of {}) {}: {}",
+                    "Prompting row {} ({} of {}) {}: {}",
+                    row + 1,
                     page,
                     page_count,
                     self.title,
                     buffer
                 );
             }
+            self.display_arrows(page, page_count, &self.three_rows_more_icon);
+            Ok(())
+        };
+
+        page_loop_intents(page_count, cur_page, draw, next_intent, options)
+    }
+
+    fn make_title_buffer(&self, page: usize, page_count: usize) -> ArrayString<16> {
+        make_title_buffer_padded(self.title, page, page_count, self.counter_digits)
+    }
+}
+
+/// Width of the space on the left of the screen kept for the glyph of
+/// `WriteScroller::with_side_glyph`, which is drawn in it from `SIDE_GLYPH_X`.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub const SIDE_GLYPH_AREA_WIDTH: usize = 26;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const SIDE_GLYPH_X: i16 = 8;
+/// Height of a row of text in the device font.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const TEXT_ROW_HEIGHT: usize = 11;
+
+/// Draw `glyph` on the left, vertically centered on the row at `y`, and `text` centered on the
+/// rest of the row.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn display_beside_glyph(glyph: &ledger_device_sdk::ui::bitmaps::Glyph, text: &str, y: usize) {
+    let glyph_y = (y + TEXT_ROW_HEIGHT / 2).saturating_sub(glyph.height as usize / 2);
+    Icon::from(glyph)
+        .set_x(SIDE_GLYPH_X)
+        .set_y(glyph_y as i16)
+        .display();
+    let space = SCREEN_WIDTH - SIDE_GLYPH_AREA_WIDTH;
+    let x = SIDE_GLYPH_AREA_WIDTH + space.saturating_sub(text_width(text, false)) / 2;
+    text.place(Location::Custom(y), Layout::Custom(x), false);
+}
+
+/// How the content of a `WriteScroller` is laid out in single-row mode.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[derive(Clone, Copy)]
+struct SingleRowLayout {
+    content_size: ContentSize,
+    /// Whether pages are found by dividing offsets, as for `uniform_pages`
+    uniform: bool,
+    page_count: usize,
+    /// Whether `with_max_pages` leaves out part of the content
+    truncated: bool,
+}
+
+/// How the content of a `WriteScroller` is laid out in three-row mode.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+struct ThreeRowsLayout {
+    subtitle_len: Option<usize>,
+    /// Row of the subtitle, below the pinned row if there is one
+    subtitle_row: usize,
+    /// Offset of the paged part of the content, and the rows it is paged through
+    body_start: usize,
+    body_rows: &'static [usize],
+    total_len: usize,
+    /// Digits of the line numbers, and the content characters left on each row
+    number_width: usize,
+    row_chars: usize,
+    /// Whether every row holds `row_chars` chars, as when the content is ASCII
+    uniform_rows: bool,
+    /// Whether pages after the first show a row of content in place of the title
+    title_on_first_page_only: bool,
+    page_count: usize,
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl ThreeRowsLayout {
+    /// First row of the body shown on `page` of the content.
+    fn first_line(&self, page: usize) -> usize {
+        let rows = self.body_rows.len();
+        if self.title_on_first_page_only && page > 0 {
+            rows + (page - 1) * (rows + 1)
+        } else {
+            page * rows
+        }
+    }
+
+    /// Page of the content on which row `line` of the body is shown, the inverse of
+    /// `first_line`.
+    fn page_of_line(&self, line: usize) -> usize {
+        let rows = self.body_rows.len();
+        if self.title_on_first_page_only && line >= rows {
+            1 + (line - rows) / (rows + 1)
+        } else {
+            line / rows
+        }
+    }
+}
+
+/// Digits needed to number every row when `content_len` characters are shown on rows of
+/// `row_len` characters, each starting with a line number and ". ", and the content characters
+/// that are left on each row.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn line_number_budget(content_len: usize, row_len: usize) -> (usize, usize) {
+    let mut digits = 1;
+    let mut limit = 10;
+    loop {
+        let row_chars = core::cmp::max(1, row_len.saturating_sub(digits + 2));
+        if page_count(content_len, row_chars, 1) < limit {
+            return (digits, row_chars);
+        }
+        digits += 1;
+        limit *= 10;
+    }
+}
+
+/// Iterator over the pages of a `WriteScroller`, see `WriteScroller::pages`.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub struct Pages<'s, 'a, S: PromptSource<CHAR_N>, const CHAR_N: usize> {
+    scroller: &'s WriteScroller<'a, S, CHAR_N>,
+    layout: SingleRowLayout,
+    page: usize,
+    known: core::cell::Cell<(usize, usize)>,
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl<'s, 'a, S: PromptSource<CHAR_N>, const CHAR_N: usize> Iterator for Pages<'s, 'a, S, CHAR_N> {
+    type Item = Result<ArrayString<CHAR_N>, ScrollerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.page >= self.layout.page_count {
+            return None;
+        }
+        let text = self
+            .scroller
+            .page_text_in(&self.layout, self.page, &self.known);
+        self.page += 1;
+        Some(text)
+    }
+}
+
+/// Vertical position of the content row in single-row mode.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const SINGLE_ROW_Y: usize = 15;
+/// Vertical positions of the content rows in three-row mode.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const THREE_ROWS_Y: [usize; 3] = [16, 31, 46];
+
+// The content rows must fit on the screen. The Nano S has no room for three rows below the
+// title, so three-row mode overlaps there.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const _: [(); 0] = [(); (SINGLE_ROW_Y + TEXT_ROW_HEIGHT > SCREEN_HEIGHT) as usize];
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[cfg(not(target_os = "nanos"))]
+const _: [(); 0] = [(); (THREE_ROWS_Y[2] + TEXT_ROW_HEIGHT > SCREEN_HEIGHT) as usize];
+/// Vertical position of the row of content shown in place of the title in three-row mode.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const TITLE_ROW_Y: usize = 1;
+
+/// What the content of a scroller writes: its length in bytes, its chars and its line breaks.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContentSize {
+    pub bytes: usize,
+    pub chars: usize,
+    pub newlines: usize,
+}
+
+/// Size of what `contents` writes.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn content_size<S: PromptSource<CHAR_N>, const CHAR_N: usize>(
+    contents: &S,
+) -> Result<ContentSize, ScrollerError> {
+    let mut buffer = ArrayString::new();
+    let mut prompt_write = PromptWrite {
+        offset: 0,
+        buffer: &mut buffer,
+        total: 0,
+        chars: 0,
+        newlines: 0,
+        overflow: &mut [],
+        full: false,
+    };
+    contents.write_page(0, &mut prompt_write)?;
+    trace!(
+        "Prompt length: {} ({} chars)",
+        prompt_write.total,
+        prompt_write.chars
+    );
+    Ok(ContentSize {
+        bytes: prompt_write.total,
+        chars: prompt_write.chars,
+        newlines: prompt_write.newlines,
+    })
+}
+
+/// Bytes at the start of `text` that fit in `limit` bytes without cutting a char, or the whole
+/// first char if not even that fits.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn fitted(text: &str, limit: usize) -> usize {
+    match floor_char_boundary(text, limit) {
+        0 => text
+            .chars()
+            .next()
+            .map_or(core::cmp::max(1, limit), char::len_utf8),
+        len => len,
+    }
+}
+
+/// Length of the page of `len` bytes at the start of `text`, written over two buffers to see
+/// the char following the page, once cut back to where it may be wrapped: if `words`, the end
+/// of its last whole word, leaving any whitespace after it on the page, and at the end of each
+/// group of `interval` chars, counted from the start of the page or of the word it ends in.
+/// The latest of these is taken, and a page with none of them is not cut back.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn wrapped_len<const CHAR_N: usize>(
+    text: &[ArrayString<CHAR_N>; 2],
+    len: usize,
+    words: bool,
+    interval: Option<usize>,
+) -> usize {
+    let page = &text[0][..len];
+    match text[0][len..].chars().chain(text[1].chars()).next() {
+        Some(next) if !(words && next.is_whitespace()) => (),
+        _ => return len,
+    }
+    let word_start = if words {
+        page.char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map(|(i, c)| i + c.len_utf8())
+    } else {
+        None
+    };
+    let group_start = word_start.unwrap_or(0);
+    let group_end = interval.and_then(|interval| {
+        let word = &page[group_start..];
+        let groups = word.chars().count() / interval;
+        if groups == 0 {
+            return None;
+        }
+        let end = word.char_indices().nth(groups * interval);
+        Some(end.map_or(len, |(i, _)| group_start + i))
+    });
+    group_end.or(word_start).unwrap_or(len)
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn make_title_buffer(title: &str, page: usize, page_count: usize) -> ArrayString<16> {
+    make_title_buffer_padded(title, page, page_count, 0)
+}
+
+/// Number of decimal digits in `n`.
+fn decimal_digits(mut n: usize) -> usize {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+/// Number of chars needed to show " (x/y)" after a title, with both numbers taking at least
+/// `counter_digits` digits.
+pub fn counter_suffix_len(page_count: usize, counter_digits: usize) -> usize {
+    4 + 2 * core::cmp::max(counter_digits, decimal_digits(page_count))
+}
+
+/// A buffer holding `title`, or if it is too long for it, both ends of it around
+/// `format::ELLIPSIS`, e.g. for a hash.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn title_buffer_for(title: &str) -> ArrayString<16> {
+    let mut title_buffer: ArrayString<16> = ArrayString::new();
+    if title_buffer.try_push_str(title).is_err() {
+        write_truncated_within(&mut title_buffer, title, 16, format::TruncateMode::Middle);
+    }
+    title_buffer
+}
+
+/// Write `text` into `buffer` as `format::write_truncated` does, keeping at most `max_chars`
+/// chars, and fewer if their bytes would not fit in `buffer`, as with non-ASCII text.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn write_truncated_within<const N: usize>(
+    buffer: &mut ArrayString<N>,
+    text: &str,
+    max_chars: usize,
+    mode: format::TruncateMode,
+) {
+    for max_chars in (0..=max_chars).rev() {
+        buffer.clear();
+        let mut w = mk_prompt_write(buffer);
+        let _ = format::write_truncated(&mut w, text, max_chars, mode);
+        if w.total <= N {
+            break;
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn make_title_buffer_padded(
+    title: &str,
+    page: usize,
+    page_count: usize,
+    counter_digits: usize,
+) -> ArrayString<16> {
+    let mut title_buffer = title_buffer_for(title);
+    let len_needed = counter_suffix_len(page_count, counter_digits);
+
+    if page_count > 1 && len_needed <= 16 && title.len() <= (16 - len_needed) {
+        // We have checked that the following will succeed, so ignore result
+        let _ = write!(
+            mk_prompt_write(&mut title_buffer),
+            " ({:>width$}/{:>width$})",
+            page + 1,
+            page_count,
+            width = counter_digits
+        );
+    }
+    title_buffer
+}
+
+/// How a scroller shows the page index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PageCounter {
+    /// Append " (x/y)" to the title, if it fits.
+    Text,
+    /// Draw "x/y" in small digit glyphs in the top left corner, leaving the title untouched.
+    Glyphs,
+    /// Append " (n%)" to the title, if it fits, with n the share of the pages seen so far.
+    Percent,
+    /// Draw a row of dots along the bottom of the screen, one per page with the current one
+    /// filled, leaving the title untouched. With more than `MAX_PAGE_DOTS` pages, this falls
+    /// back to `Text`.
+    Dots,
+}
+
+/// Share of the pages seen on `page`, in percent, reaching 100 on the last page.
+pub fn percent_seen(page: usize, page_count: usize) -> usize {
+    (page + 1) * 100 / core::cmp::max(1, page_count)
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn make_percent_title_buffer(title: &str, page: usize, page_count: usize) -> ArrayString<16> {
+    let mut title_buffer = title_buffer_for(title);
+    // Number of chars needed to show " (100%)"
+    let len_needed = 7;
+
+    if page_count > 1 && title.len() <= (16 - len_needed) {
+        // We have checked that the following will succeed, so ignore result
+        let _ = write!(
+            mk_prompt_write(&mut title_buffer),
+            " ({}%)",
+            percent_seen(page, page_count)
+        );
+    }
+    title_buffer
+}
+
+/// Draw `n` in small digit glyphs starting at `x`, returning the x just past it.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn display_small_number(mut n: usize, mut x: i16, y: i16) -> i16 {
+    let mut divisor = 1;
+    while divisor * 10 <= n {
+        divisor *= 10;
+    }
+    while divisor > 0 {
+        Icon::from(&bitmaps::SMALL_DIGIT_GLYPHS[n / divisor])
+            .set_x(x)
+            .set_y(y)
+            .display();
+        x += bitmaps::SMALL_DIGIT_WIDTH + 1;
+        n %= divisor;
+        divisor /= 10;
+    }
+    x
+}
+
+/// Most pages whose index `PageCounter::Dots` shows as dots.
+pub const MAX_PAGE_DOTS: usize = 16;
+/// Distance between the left edges of two page dots.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const PAGE_DOT_PITCH: usize = 6;
+#[cfg(target_os = "nanos")]
+const PAGE_DOTS_Y: usize = 29;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[cfg(not(target_os = "nanos"))]
+const PAGE_DOTS_Y: usize = 59;
+
+/// Horizontal position of the dot of `page` in a row of `page_count` centered dots.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn page_dot_x(page: usize, page_count: usize) -> usize {
+    let width = (page_count - 1) * PAGE_DOT_PITCH + bitmaps::PAGE_DOT_GLYPH.width as usize;
+    SCREEN_WIDTH.saturating_sub(width) / 2 + page * PAGE_DOT_PITCH
+}
+
+/// Glyph of the dot of `dot` when on `page`.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn page_dot_glyph(
+    dot: usize,
+    page: usize,
+) -> &'static ledger_device_sdk::ui::bitmaps::Glyph<'static> {
+    if dot == page {
+        &bitmaps::PAGE_DOT_GLYPH
+    } else {
+        &bitmaps::PAGE_DOT_EMPTY_GLYPH
+    }
+}
+
+/// Draw one dot per page along the bottom of the screen, filling the one of `page`.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn display_page_dots(page: usize, page_count: usize) {
+    for dot in 0..page_count {
+        Icon::from(page_dot_glyph(dot, page))
+            .set_x(page_dot_x(dot, page_count) as i16)
+            .set_y(PAGE_DOTS_Y as i16)
+            .display();
+    }
+}
+
+/// Draw the index of `page` in small digit glyphs in the top left corner, returning the x just
+/// past it.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn display_glyph_counter(page: usize, page_count: usize) -> i16 {
+    let x = display_small_number(page + 1, 1, 1);
+    Icon::from(&bitmaps::SMALL_SLASH_GLYPH)
+        .set_x(x)
+        .set_y(1)
+        .display();
+    display_small_number(page_count, x + bitmaps::SMALL_DIGIT_WIDTH + 1, 1)
+}
+
+/// Draw `title` at the top of the screen clear of the first `left` pixels: centered on the
+/// screen if that keeps it clear of them, and otherwise centered on the rest of the row, cut
+/// short if it does not fit there.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn place_title_beside(title: &str, left: usize, bold: bool) {
+    let space = SCREEN_WIDTH.saturating_sub(left);
+    let mut shortened: ArrayString<16> = ArrayString::new();
+    let title = if text_width(title, bold) <= space {
+        title
+    } else {
+        shorten_to_fit(
+            &mut shortened,
+            title,
+            bold,
+            space,
+            format::TruncateMode::Head,
+        );
+        shortened.as_str()
+    };
+    let width = text_width(title, bold);
+    let centered = SCREEN_WIDTH.saturating_sub(width) / 2;
+    let x = if centered >= left {
+        centered
+    } else {
+        left + space.saturating_sub(width) / 2
+    };
+    title.place(Location::Top, Layout::Custom(x), bold);
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub const SCREEN_WIDTH: usize = 128;
+
+/// Glyph metrics of a font, used for layout computations.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub trait FontMetrics {
+    /// Horizontal advance in pixels of `c`.
+    fn advance(&self, c: char, bold: bool) -> usize;
+
+    fn text_width(&self, text: &str, bold: bool) -> usize {
+        text.chars().map(|c| self.advance(c, bold)).sum()
+    }
+}
+
+/// Metrics of the SDK's built-in font, which is what `Label` draws with.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub struct DeviceFont;
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl FontMetrics for DeviceFont {
+    fn advance(&self, c: char, bold: bool) -> usize {
+        let mut buffer = [0; 4];
+        let s: &str = c.encode_utf8(&mut buffer);
+        s.compute_width(bold)
+    }
+
+    fn text_width(&self, text: &str, bold: bool) -> usize {
+        text.compute_width(bold)
+    }
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+static FONT_METRICS: core::sync::atomic::AtomicPtr<&'static (dyn FontMetrics + Sync)> =
+    core::sync::atomic::AtomicPtr::new(core::ptr::null_mut());
+
+/// Use `metrics` for the width of text in all layout from now on, e.g. for hardware drawing
+/// with another font than the SDK's. Call this once at startup, with the metrics kept in a
+/// `static` such as `static METRICS: &(dyn FontMetrics + Sync) = &MyFont;`.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn set_font_metrics(metrics: &'static &'static (dyn FontMetrics + Sync)) {
+    let metrics = metrics as *const &'static (dyn FontMetrics + Sync);
+    FONT_METRICS.store(metrics as *mut _, core::sync::atomic::Ordering::Relaxed);
+}
+
+/// The metrics set with `set_font_metrics`, or `DeviceFont` if there are none.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn font_metrics() -> &'static dyn FontMetrics {
+    let metrics = FONT_METRICS.load(core::sync::atomic::Ordering::Relaxed);
+    if metrics.is_null() {
+        &DeviceFont
+    } else {
+        // Only ever set from a &'static reference in set_font_metrics
+        unsafe { *metrics }
+    }
+}
+
+/// Metrics of a layout that gives every character a cell of the same width.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub struct MonospaceFont {
+    pub cell_width: usize,
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl FontMetrics for MonospaceFont {
+    fn advance(&self, _c: char, _bold: bool) -> usize {
+        self.cell_width
+    }
+}
+
+/// Cell width used for monospace content: the widest of the digits and hex letters in the
+/// font of `font_metrics`. Wider characters overlap their neighbours.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn monospace_cell_width() -> usize {
+    "0123456789abcdefABCDEF"
+        .chars()
+        .map(|c| font_metrics().advance(c, false))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Draw `text` centered on the line at `y`, one character per cell of `cell_width` pixels.
+///
+/// The SDK has no monospace font, so each character is placed on its own, centered in its cell.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn place_monospace(text: &str, y: usize, cell_width: usize) {
+    let width = (MonospaceFont { cell_width }).text_width(text, false);
+    let mut x = SCREEN_WIDTH.saturating_sub(width) / 2;
+    let mut buffer = [0; 4];
+    for c in text.chars() {
+        let s: &str = c.encode_utf8(&mut buffer);
+        let advance = font_metrics().advance(c, false);
+        s.place(
+            Location::Custom(y),
+            Layout::Custom(x + cell_width.saturating_sub(advance) / 2),
+            false,
+        );
+        x += cell_width;
+    }
+}
+
+/// Width in pixels of `text` as drawn by a `Label`, in the font of `font_metrics`. This is
+/// the width that all layout goes by.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn text_width(text: &str, bold: bool) -> usize {
+    font_metrics().text_width(text, bold)
+}
+
+/// Whether `title` is shown in full as the title of a scroller rather than being clipped.
+/// Titles are limited both by the title buffer and by the width of the screen.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn title_fits(title: &str) -> bool {
+    title.len() <= 16 && text_width(title, false) <= SCREEN_WIDTH
+}
+
+/// Like `title_fits`, for a display using the font described by `metrics`.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn title_fits_with(metrics: &impl FontMetrics, title: &str) -> bool {
+    title.len() <= 16 && metrics.text_width(title, false) <= SCREEN_WIDTH
+}
+
+/// What the user asked for, independent of the physical input that produced it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavIntent {
+    /// The input for `Prev` has started, e.g. the left button is down. Only used for feedback.
+    PrevPressed,
+    /// The input for `Next` has started. Only used for feedback.
+    NextPressed,
+    /// The input for `Cancel` has started, e.g. both buttons are down. Only used for feedback.
+    CancelPressed,
+    Prev,
+    Next,
+    Select,
+    Cancel,
+}
+
+impl NavIntent {
+    /// Map a button event to the intent it has in a scroller: the left and right buttons move
+    /// between pages and both buttons cancel.
+    pub fn from_button(event: ButtonEvent) -> Option<NavIntent> {
+        match event {
+            ButtonEvent::LeftButtonPress => Some(NavIntent::PrevPressed),
+            ButtonEvent::RightButtonPress => Some(NavIntent::NextPressed),
+            ButtonEvent::LeftButtonRelease => Some(NavIntent::Prev),
+            ButtonEvent::RightButtonRelease => Some(NavIntent::Next),
+            ButtonEvent::BothButtonsRelease => Some(NavIntent::Cancel),
+            ButtonEvent::BothButtonsPress => Some(NavIntent::CancelPressed),
+        }
+    }
+}
+
+/// Input from a rotary jog, for device variants that have one.
+#[cfg(feature = "jog")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JogEvent {
+    Backward,
+    Forward,
+    Press,
+}
+
+#[cfg(feature = "jog")]
+impl From<JogEvent> for NavIntent {
+    fn from(event: JogEvent) -> NavIntent {
+        match event {
+            JogEvent::Backward => NavIntent::Prev,
+            JogEvent::Forward => NavIntent::Next,
+            JogEvent::Press => NavIntent::Select,
+        }
+    }
+}
+
+/// Run the button loop for a paged prompt, drawing pages with `draw`. Moving right past the
+/// last page accepts, and both buttons reject.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn page_loop(
+    page_count: usize,
+    draw: impl Fn(usize) -> Result<(), ScrollerError>,
+) -> Result<bool, ScrollerError> {
+    let mut buttons = ButtonsState::new();
+    page_loop_intents(
+        page_count,
+        &mut 0,
+        draw,
+        button_intents(&mut buttons),
+        PageLoopOptions::DEFAULT,
+    )
+    .map(|decision| decision == Decision::Accept)
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn button_intents(buttons: &mut ButtonsState) -> impl FnMut() -> Option<NavIntent> + '_ {
+    event_intents(move || get_event(buttons))
+}
+
+/// The intents of the button events from `next_event`, with `None` for a tick.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn event_intents(
+    mut next_event: impl FnMut() -> Option<ButtonEvent>,
+) -> impl FnMut() -> Option<NavIntent> {
+    move || next_event().and_then(NavIntent::from_button)
+}
+
+/// Wrap `next_intent` so that a `Prev` and a `Next` less than `window` ticks apart, in either
+/// order, become a single `Cancel`, as from both buttons. Each `Prev` or `Next` is held back
+/// until the window has passed or the next one arrives. A `window` of 0 changes nothing.
+pub fn coalesce_both_buttons(
+    window: usize,
+    mut next_intent: impl FnMut() -> Option<NavIntent>,
+) -> impl FnMut() -> Option<NavIntent> {
+    // The intent held back, and the ticks seen since
+    let mut pending: Option<(NavIntent, usize)> = None;
+    move || {
+        if window == 0 {
+            return next_intent();
+        }
+        match (next_intent(), pending) {
+            (Some(intent @ (NavIntent::Prev | NavIntent::Next)), Some((held, _))) => {
+                if intent != held {
+                    trace!("Coalescing single presses into both buttons");
+                    pending = None;
+                    Some(NavIntent::Cancel)
+                } else {
+                    pending = Some((intent, 0));
+                    Some(held)
+                }
+            }
+            (Some(intent @ (NavIntent::Prev | NavIntent::Next)), None) => {
+                pending = Some((intent, 0));
+                None
+            }
+            (Some(NavIntent::Cancel), _) => {
+                pending = None;
+                Some(NavIntent::Cancel)
+            }
+            (None, Some((held, ticks))) if ticks + 1 >= window => {
+                pending = None;
+                Some(held)
+            }
+            (None, Some((held, ticks))) => {
+                pending = Some((held, ticks + 1));
+                None
+            }
+            (intent, _) => intent,
+        }
+    }
+}
+
+/// What pressing both buttons does in a scroller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BothButtonAction {
+    /// Reject the prompt from any page.
+    Reject,
+    /// Accept the prompt on the last page, and do nothing on earlier pages.
+    ConfirmOnLast,
+    /// Do nothing.
+    Ignore,
+}
+
+/// What happens when a scroller reaches its last page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndBehavior {
+    /// Accept as soon as the last page is reached by moving right. A prompt that starts on its
+    /// last page, as one with a single page does, still waits for the user to move past it.
+    AutoConfirm,
+    /// Wait on the last page for the user to move past it.
+    RequirePress,
+}
+
+/// Number of idle events, which are mostly ticker events, after which a held button counts as
+/// a long press.
+pub const LONG_PRESS_TICKS: usize = 8;
+
+/// Behaviour of the button loop of a scroller.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[derive(Clone, Copy)]
+struct PageLoopOptions {
+    both_buttons: BothButtonAction,
+    review_again: bool,
+    end_behavior: EndBehavior,
+    direction_hints: bool,
+    exit_on_left_at_start: bool,
+    both_buttons_window: usize,
+    require_scroll: bool,
+    /// Idle ticks after which the prompt is rejected
+    idle_timeout: Option<usize>,
+    long_press_jump: bool,
+    /// Ticks a button is held before its move repeats, and ticks between repeats
+    auto_repeat: Option<(usize, usize)>,
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl PageLoopOptions {
+    const DEFAULT: PageLoopOptions = PageLoopOptions {
+        both_buttons: BothButtonAction::Reject,
+        review_again: false,
+        end_behavior: EndBehavior::RequirePress,
+        direction_hints: false,
+        exit_on_left_at_start: false,
+        both_buttons_window: 0,
+        require_scroll: false,
+        idle_timeout: None,
+        long_press_jump: false,
+        auto_repeat: None,
+    };
+}
+
+/// Like `page_loop`, but taking navigation intents from `next_intent` and starting on page
+/// `*cur_page`, which is left at the last page shown. Moving past the last page or selecting on
+/// it accepts, as does moving right onto it with `EndBehavior::AutoConfirm`, and cancel is
+/// handled according to `options.both_buttons`. With `options.exit_on_left_at_start`, moving
+/// left from the first page returns `Decision::Back`.
+///
+/// `next_intent` returning `None` is counted as a tick, to detect long presses.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn page_loop_intents(
+    page_count: usize,
+    cur_page: &mut usize,
+    draw: impl Fn(usize) -> Result<(), ScrollerError>,
+    mut next_intent: impl FnMut() -> Option<NavIntent>,
+    options: PageLoopOptions,
+) -> Result<Decision, ScrollerError> {
+    *cur_page = core::cmp::min(*cur_page, page_count - 1);
+    // Ticks seen while the left or the right button is held, if it is, and the ticks it was
+    // held for when it was last released. These follow the buttons as they are pressed and
+    // released, before a release is held back to be coalesced with the other button.
+    let prev_held: core::cell::Cell<Option<usize>> = core::cell::Cell::new(None);
+    let next_held: core::cell::Cell<Option<usize>> = core::cell::Cell::new(None);
+    let prev_released: core::cell::Cell<Option<usize>> = core::cell::Cell::new(None);
+    let next_released: core::cell::Cell<Option<usize>> = core::cell::Cell::new(None);
+    // Ticks since the last intent, and whether the last one was a tick. Like the ticks above,
+    // these are counted before intents are held back, so that a held back release still
+    // counts as touching a button, and the `None` returned for it is not a tick.
+    let idle_ticks = core::cell::Cell::new(0);
+    let ticked = core::cell::Cell::new(false);
+    let mut next_intent = coalesce_both_buttons(options.both_buttons_window, || {
+        let intent = next_intent();
+        ticked.set(intent.is_none());
+        idle_ticks.set(match intent {
+            None => idle_ticks.get() + 1,
+            Some(_) => 0,
+        });
+        match intent {
+            None => {
+                prev_held.set(prev_held.get().map(|ticks| ticks + 1));
+                next_held.set(next_held.get().map(|ticks| ticks + 1));
+            }
+            Some(NavIntent::PrevPressed) => prev_held.set(Some(0)),
+            Some(NavIntent::NextPressed) => next_held.set(Some(0)),
+            Some(NavIntent::Prev) => prev_released.set(prev_held.take()),
+            Some(NavIntent::Next) => next_released.set(next_held.take()),
+            Some(NavIntent::Cancel) => {
+                prev_held.set(None);
+                next_held.set(None);
+            }
+            Some(NavIntent::CancelPressed | NavIntent::Select) => (),
+        }
+        intent
+    });
+    // Whether the button held has repeated its move, so that releasing it moves no further
+    let mut repeated = false;
+    let repeats = |held: Option<usize>| {
+        matches!((options.auto_repeat, held), (Some((delay, interval)), Some(ticks))
+            if ticks >= delay && (ticks - delay) % interval == 0)
+    };
+    // Whether a direction hint is shown and should be cleared on the next tick
+    let mut hint_shown = false;
+    // Whether the last page was reached by moving right, or need not be
+    let mut end_reached = !options.require_scroll;
+    // Until it is, the last page shows an arrow in place of the check
+    let draw = |page: usize, end_reached: bool| -> Result<(), ScrollerError> {
+        draw(page)?;
+        if !end_reached && page + 1 == page_count {
+            RIGHT_ARROW.instant_display();
+        }
+        Ok(())
+    };
+
+    draw(*cur_page, end_reached)?;
+    let auto_confirm = options.end_behavior == EndBehavior::AutoConfirm;
+
+    loop {
+        let intent = next_intent();
+        if intent.is_none() && ticked.get() {
+            if matches!(options.idle_timeout, Some(timeout) if idle_ticks.get() >= timeout) {
+                trace!("Prompt timed out");
+                break Ok(Decision::Reject);
+            }
+            if hint_shown {
+                hint_shown = false;
+                draw(*cur_page, end_reached)?;
+            }
+            if repeats(prev_held.get()) && *cur_page > 0 {
+                *cur_page -= 1;
+                repeated = true;
+                draw(*cur_page, end_reached)?;
+                LEFT_S_ARROW.instant_display();
+            } else if repeats(next_held.get()) && *cur_page + 1 < page_count {
+                *cur_page += 1;
+                if *cur_page + 1 == page_count {
+                    end_reached = true;
+                }
+                repeated = true;
+                draw(*cur_page, end_reached)?;
+                RIGHT_S_ARROW.instant_display();
+            }
+        }
+        // Whether a move ends a long press of its button
+        let long_press = match intent {
+            Some(NavIntent::Prev) => {
+                matches!(prev_released.take(), Some(ticks) if ticks >= LONG_PRESS_TICKS)
+            }
+            Some(NavIntent::Next) => {
+                matches!(next_released.take(), Some(ticks) if ticks >= LONG_PRESS_TICKS)
+            }
+            _ => false,
+        };
+        match intent {
+            Some(NavIntent::Prev | NavIntent::Next) if repeated => {
+                repeated = false;
+                // Redraw to clear the button press arrow
+                draw(*cur_page, end_reached)?;
+                if auto_confirm && end_reached && *cur_page + 1 == page_count {
+                    trace!("Reached last page, confirming");
+                    break Ok(Decision::Accept);
+                }
+            }
+            Some(NavIntent::PrevPressed) => {
+                repeated = false;
+                LEFT_S_ARROW.instant_display();
+            }
+            Some(NavIntent::Prev)
+                if long_press
+                    && *cur_page > 0
+                    && (options.long_press_jump
+                        || options.review_again && *cur_page + 1 == page_count) =>
+            {
+                trace!("Going back to the first page");
+                *cur_page = 0;
+                draw(*cur_page, end_reached)?;
+            }
+            Some(NavIntent::NextPressed) => {
+                repeated = false;
+                RIGHT_S_ARROW.instant_display();
+            }
+            Some(NavIntent::Next)
+                if long_press
+                    && options.long_press_jump
+                    && !options.require_scroll
+                    && *cur_page + 1 < page_count =>
+            {
+                trace!("Going to the last page");
+                *cur_page = page_count - 1;
+                draw(*cur_page, end_reached)?;
+                if auto_confirm {
+                    trace!("Reached last page, confirming");
+                    break Ok(Decision::Accept);
+                }
+            }
+            Some(NavIntent::Prev) if options.exit_on_left_at_start && *cur_page == 0 => {
+                trace!("Leaving from the first page");
+                break Ok(Decision::Back);
+            }
+            Some(NavIntent::Prev) => {
+                let moved = *cur_page > 0;
+                if moved {
+                    *cur_page -= 1;
+                }
+                // We need to draw anyway to clear button press arrow
+                draw(*cur_page, end_reached)?;
+                if moved && options.direction_hints {
+                    LEFT_S_ARROW.instant_display();
+                    hint_shown = true;
+                }
+            }
+            Some(NavIntent::Next) if !end_reached && *cur_page + 1 == page_count => {
+                trace!("Last page seen, offering to accept");
+                end_reached = true;
+                draw(*cur_page, end_reached)?;
+            }
+            Some(NavIntent::Next) => {
+                if *cur_page + 1 == page_count {
+                    break Ok(Decision::Accept);
+                }
+                *cur_page += 1;
+                if *cur_page + 1 == page_count {
+                    end_reached = true;
+                }
+                // We need to draw anyway to clear button press arrow
+                draw(*cur_page, end_reached)?;
+                if options.direction_hints {
+                    RIGHT_S_ARROW.instant_display();
+                    hint_shown = true;
+                }
+                if auto_confirm && *cur_page + 1 == page_count {
+                    trace!("Reached last page, confirming");
+                    break Ok(Decision::Accept);
+                }
+            }
+            Some(NavIntent::Select) if end_reached && *cur_page + 1 == page_count => {
+                break Ok(Decision::Accept)
+            }
+            Some(NavIntent::Cancel) => match options.both_buttons {
+                BothButtonAction::Reject => break Ok(Decision::Reject),
+                BothButtonAction::ConfirmOnLast if end_reached && *cur_page + 1 == page_count => {
+                    break Ok(Decision::Accept)
+                }
+                BothButtonAction::ConfirmOnLast | BothButtonAction::Ignore => {
+                    // Redraw to clear button press arrows
+                    draw(*cur_page, end_reached)?;
+                }
+            },
+            Some(_) | None => (),
+        }
+    }
+}
+
+pub type SectionContents<'a, const CHAR_N: usize> =
+    &'a dyn for<'b> Fn(&mut PromptWrite<'b, CHAR_N>) -> Result<(), ScrollerError>;
+
+/// One group of a `SectionScroller`: a header line followed by its content.
+pub struct Section<'a, const CHAR_N: usize> {
+    pub header: &'a str,
+    pub contents: SectionContents<'a, CHAR_N>,
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+enum SectionRow<'a> {
+    Header(&'a str),
+    /// The section, and the row of its content
+    Content(usize, usize),
+    Blank,
+}
+
+/// Most sections that a `SectionScroller` pages through.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub const MAX_SECTIONS: usize = 16;
+
+/// Pages through several sections using the three-row layout. Each section is shown as a bold
+/// header row followed by its content rows, a new one starting at each `\n` in the content.
+///
+/// By default sections follow each other directly, so a page may hold the end of one section
+/// and the header of the next. With `with_page_per_section`, every header starts a new page and
+/// the rest of the previous page is left blank.
+///
+/// With more than `MAX_SECTIONS` sections, `ask_err` fails without showing any of them.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub struct SectionScroller<'a, const CHAR_N: usize> {
+    title: &'a str,
+    sections: &'a [Section<'a, CHAR_N>],
+    page_per_section: bool,
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl<'a, const CHAR_N: usize> SectionScroller<'a, CHAR_N> {
+    pub fn new(title: &'a str, sections: &'a [Section<'a, CHAR_N>]) -> Self {
+        SectionScroller {
+            title,
+            sections,
+            page_per_section: false,
+        }
+    }
+
+    pub fn with_page_per_section(self, page_per_section: bool) -> Self {
+        SectionScroller {
+            page_per_section,
+            ..self
+        }
+    }
+
+    /// The content rows of `section`, as the pages of a single-row scroller: each row ends at
+    /// a line break, or at the last char that fits.
+    fn content_rows(
+        section: &Section<'a, CHAR_N>,
+    ) -> WriteScroller<'a, SectionContents<'a, CHAR_N>, CHAR_N> {
+        WriteScroller::new(section.header, section.contents).with_page_break_char('\n')
+    }
+
+    /// Number of rows taken by a section with `content_rows` rows of content, including its
+    /// header and any padding.
+    fn section_rows(&self, content_rows: usize) -> usize {
+        let rows = 1 + content_rows;
+        if self.page_per_section {
+            (rows - 1) / THREE_ROWS_Y.len() * THREE_ROWS_Y.len() + THREE_ROWS_Y.len()
+        } else {
+            rows
+        }
+    }
+
+    /// What is shown on `row`, given the number of content rows of each section.
+    fn locate_row(&self, content_rows: &[usize], mut row: usize) -> SectionRow<'a> {
+        for (i, (section, &content_rows)) in self.sections.iter().zip(content_rows).enumerate() {
+            let rows = self.section_rows(content_rows);
+            if row < rows {
+                return if row == 0 {
+                    SectionRow::Header(section.header)
+                } else if row - 1 < content_rows {
+                    SectionRow::Content(i, row - 1)
+                } else {
+                    SectionRow::Blank
+                };
+            }
+            row -= rows;
+        }
+        SectionRow::Blank
+    }
+
+    pub fn ask(&self, show_index: bool) -> bool {
+        self.ask_err(show_index).unwrap_or(false)
+    }
+
+    pub fn ask_err(&self, show_index: bool) -> Result<bool, ScrollerError> {
+        if self.sections.len() > MAX_SECTIONS {
+            trace!("Too many sections: {}", self.sections.len());
+            return Err(ScrollerError::Overflow);
+        }
+        // The content rows of each section are counted once, rather than on every draw
+        let mut content_rows: arrayvec::ArrayVec<usize, MAX_SECTIONS> = arrayvec::ArrayVec::new();
+        for section in self.sections {
+            content_rows.push(Self::content_rows(section).page_count()?);
+        }
+        let total_rows = content_rows
+            .iter()
+            .map(|&rows| self.section_rows(rows))
+            .sum();
+        let page_count = crate::page_count(total_rows, 1, THREE_ROWS_Y.len());
+        if page_count > MAX_PAGE_COUNT {
+            trace!("Page count too large: {}", page_count);
+            return Err(ScrollerError::TooManyPages);
+        }
+
+        let draw = |page: usize| -> Result<(), ScrollerError> {
+            clear_screen();
+            if show_index {
+                let title_buffer = make_title_buffer(self.title, page, page_count);
+                let title_label: Label = From::from(title_buffer.as_str());
+                title_label.location(Location::Top).display();
+            } else {
+                let title_label: Label = From::from(self.title);
+                title_label.location(Location::Top).display();
+            };
+            for (row, y) in THREE_ROWS_Y.iter().enumerate() {
+                match self.locate_row(&content_rows, page * THREE_ROWS_Y.len() + row) {
+                    SectionRow::Header(header) => {
+                        header.place(Location::Custom(*y), Layout::Centered, true);
+                        trace!("Prompting section {}", header);
+                    }
+                    SectionRow::Content(section, content_row) => {
+                        let buffer =
+                            Self::content_rows(&self.sections[section]).page_text(content_row)?;
+                        let label: Label = From::from(buffer.as_str());
+                        label.location(Location::Custom(*y)).display();
+                        trace!(
+                            "Prompting row {} ({} of {}) {}: {}",
+                            row + 1,
+                            page,
+                            page_count,
+                            self.title,
+                            buffer
+                        );
+                    }
+                    SectionRow::Blank => (),
+                }
+            }
             if page > 0 {
                 LEFT_ARROW.instant_display();
             }
@@ -323,121 +3196,374 @@ impl<
             Ok(())
         };
 
-        draw(cur_page)?;
+        page_loop(page_count, draw)
+    }
+}
 
-        loop {
-            match get_event(&mut buttons) {
-                Some(ButtonEvent::LeftButtonPress) => {
-                    LEFT_S_ARROW.instant_display();
-                }
-                Some(ButtonEvent::RightButtonPress) => {
-                    RIGHT_S_ARROW.instant_display();
-                }
-                Some(ButtonEvent::LeftButtonRelease) => {
-                    if cur_page > 0 {
-                        cur_page -= 1;
-                    }
-                    // We need to draw anyway to clear button press arrow
-                    draw(cur_page)?;
-                }
-                Some(ButtonEvent::RightButtonRelease) => {
-                    if cur_page < page_count {
-                        cur_page += 1;
-                    }
-                    if cur_page == page_count {
-                        break Ok(true);
-                    }
-                    // We need to draw anyway to clear button press arrow
-                    draw(cur_page)?;
-                }
-                Some(ButtonEvent::BothButtonsRelease) => break Ok(false),
-                Some(_) | None => (),
-            }
-        }
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[inline(never)]
+pub fn write_sections(
+    show_index: bool,
+    title: &str,
+    sections: &[Section<'_, 16>],
+    page_per_section: bool,
+) -> Option<()> {
+    if !SectionScroller::new(title, sections)
+        .with_page_per_section(page_per_section)
+        .ask(show_index)
+    {
+        trace!("User rejected prompt");
+        None
+    } else {
+        Some(())
     }
+}
 
-    fn make_title_buffer(&self, page: usize, page_count: usize) -> ArrayString<16> {
-        let mut title_buffer: ArrayString<16> = ArrayString::new();
-        // Number of chars needed to show " (x/y)"
-        let len_needed = 4 + if page_count < 10 {
-            2
-        } else if page_count < 100 {
-            4
-        } else {
-            6
-        };
+/// A "Processing." / ".." / "..." screen for long blocking operations.
+///
+/// Nothing drives the animation on its own; call `tick` between I/O waits to advance it.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub struct EllipsisAnimation<'a> {
+    text: &'a str,
+    frame: usize,
+}
 
-        title_buffer.push_str(self.title);
-        if page_count > 1 && self.title.len() <= (16 - len_needed) {
-            // We have checked that the following will succeed, so ignore result
-            let _ = write!(
-                mk_prompt_write(&mut title_buffer),
-                " ({}/{})",
-                page + 1,
-                page_count
-            );
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl<'a> EllipsisAnimation<'a> {
+    pub const FRAMES: usize = 3;
+
+    pub fn new(text: &'a str) -> Self {
+        EllipsisAnimation { text, frame: 0 }
+    }
+
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Advance to the next frame and redraw.
+    pub fn tick(&mut self) {
+        self.frame = (self.frame + 1) % Self::FRAMES;
+        self.show();
+    }
+
+    pub fn show(&self) {
+        clear_screen();
+        let mut buffer: ArrayString<16> = ArrayString::new();
+        let mut writer = mk_prompt_write(&mut buffer);
+        // Pad with spaces so the centered text doesn't move as the dots grow
+        let _ = writer.write_str(self.text);
+        for _ in 0..=self.frame {
+            let _ = writer.write_str(".");
         }
-        title_buffer
+        for _ in self.frame + 1..Self::FRAMES {
+            let _ = writer.write_str(" ");
+        }
+        Label::from(buffer.as_str())
+            .location(Location::Middle)
+            .instant_display();
     }
 }
 
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 pub const MENU_ICON_X: i16 = 55;
 #[cfg(target_os = "nanos")]
 pub const MENU_ICON_Y: i16 = 2;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 #[cfg(not(target_os = "nanos"))]
 pub const MENU_ICON_Y: i16 = 15;
 
 #[cfg(target_os = "nanos")]
 pub const MENU_TOP_TEXT_Y: usize = 0;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 #[cfg(not(target_os = "nanos"))]
 pub const MENU_TOP_TEXT_Y: usize = 15;
 
 #[cfg(target_os = "nanos")]
 pub const MENU_BOTTOM_TEXT_Y: usize = 15;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 #[cfg(not(target_os = "nanos"))]
 pub const MENU_BOTTOM_TEXT_Y: usize = 35;
 
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 pub const BACK_ICON: Icon = Icon::from(&bitmaps::BACK_GLYPH)
     .set_x(MENU_ICON_X)
     .set_y(MENU_ICON_Y);
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 pub const DASHBOARD_ICON: Icon = Icon::from(&bitmaps::DASHBOARD_GLYPH)
     .set_x(MENU_ICON_X)
     .set_y(MENU_ICON_Y);
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 pub const SETTINGS_ICON: Icon = Icon::from(&bitmaps::SETTINGS_GLYPH)
     .set_x(MENU_ICON_X)
     .set_y(MENU_ICON_Y);
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub const WARNING_ICON: Icon = Icon::from(&bitmaps::WARNING_GLYPH)
+    .set_x(MENU_ICON_X)
+    .set_y(MENU_ICON_Y);
 
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 pub enum MenuLabelTop<'a> {
     Icon(&'a Icon<'a>),
     Text(&'a str),
 }
 
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 pub struct MenuLabelBottom<'a> {
     pub text: &'a str,
     pub bold: bool,
 }
 
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 pub trait Menu {
     type BothResult;
     fn move_left(&mut self);
     fn move_right(&mut self);
     fn handle_both(&mut self) -> Option<Self::BothResult>;
     fn label<'a>(&self) -> (MenuLabelTop<'a>, MenuLabelBottom<'a>);
+    /// A glyph drawn on the left of the label, e.g. `bitmaps::SETTINGS_GLYPH`.
+    fn icon(&self) -> Option<&ledger_device_sdk::ui::bitmaps::Glyph<'_>> {
+        None
+    }
+    /// Index of the current item and number of items, as shown by `show_menu_with_position`.
+    fn index(&self) -> (usize, usize) {
+        (0, 1)
+    }
+}
+
+/// One item of a `BoundedMenu`: its labels, and what selecting it with both buttons returns,
+/// if anything.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub struct MenuItem<'l, T> {
+    pub top: MenuLabelTop<'l>,
+    pub bottom: MenuLabelBottom<'l>,
+    pub result: Option<T>,
+}
+
+/// A menu of a fixed list of items. Moving past the first or the last item goes round to the
+/// other end with `wrap`, and otherwise stays on it.
+///
+/// As the labels of `Menu::label` may outlive the menu, it is only a `Menu` with `'static`
+/// labels. With labels borrowed for less, e.g. from a request, use `handle_button` and `show`.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub struct BoundedMenu<'a, 'l, T> {
+    items: &'a [MenuItem<'l, T>],
+    index: usize,
+    wrap: bool,
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl<'a, 'l, T: Copy> BoundedMenu<'a, 'l, T> {
+    pub fn new(items: &'a [MenuItem<'l, T>], wrap: bool) -> Self {
+        BoundedMenu {
+            items,
+            index: 0,
+            wrap,
+        }
+    }
+
+    /// The labels of the current item, or empty ones if there are no items.
+    pub fn labels(&self) -> (MenuLabelTop<'l>, MenuLabelBottom<'l>) {
+        match self.items.get(self.index) {
+            Some(item) => (
+                match item.top {
+                    MenuLabelTop::Icon(icon) => MenuLabelTop::Icon(icon),
+                    MenuLabelTop::Text(text) => MenuLabelTop::Text(text),
+                },
+                MenuLabelBottom {
+                    text: item.bottom.text,
+                    bold: item.bottom.bold,
+                },
+            ),
+            None => (
+                MenuLabelTop::Text(""),
+                MenuLabelBottom {
+                    text: "",
+                    bold: false,
+                },
+            ),
+        }
+    }
+
+    /// Show the current item, as `show_menu` does.
+    pub fn show(&self) {
+        clear_screen();
+        let (top, bottom) = self.labels();
+        display_menu_labels(top, bottom, None);
+    }
+
+    /// Handle `btn`, as `handle_menu_button_action` does.
+    pub fn handle_button(&mut self, btn: ButtonEvent) -> MenuAction<T> {
+        match btn {
+            ButtonEvent::LeftButtonRelease => {
+                self.go_left();
+                MenuAction::Moved
+            }
+            ButtonEvent::RightButtonRelease => {
+                self.go_right();
+                MenuAction::Moved
+            }
+            ButtonEvent::BothButtonsRelease => match self.selected() {
+                Some(result) => MenuAction::Selected(result),
+                None => MenuAction::Moved,
+            },
+            _ => MenuAction::None,
+        }
+    }
+
+    fn go_left(&mut self) {
+        if self.index > 0 {
+            self.index -= 1;
+        } else if self.wrap {
+            self.index = self.items.len().saturating_sub(1);
+        }
+    }
+
+    fn go_right(&mut self) {
+        if self.index + 1 < self.items.len() {
+            self.index += 1;
+        } else if self.wrap {
+            self.index = 0;
+        }
+    }
+
+    fn selected(&self) -> Option<T> {
+        self.items.get(self.index)?.result
+    }
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl<'a, T: Copy> Menu for BoundedMenu<'a, 'static, T> {
+    type BothResult = T;
+
+    fn move_left(&mut self) {
+        self.go_left()
+    }
+
+    fn move_right(&mut self) {
+        self.go_right()
+    }
+
+    fn handle_both(&mut self) -> Option<T> {
+        self.selected()
+    }
+
+    fn index(&self) -> (usize, usize) {
+        (self.index, self.items.len())
+    }
+
+    fn label<'b>(&self) -> (MenuLabelTop<'b>, MenuLabelBottom<'b>) {
+        self.labels()
+    }
 }
 
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 #[inline(never)]
 pub fn show_menu<M: Menu>(menu: &M) {
+    display_menu(menu, false, false)
+}
+
+/// Like `show_menu`, but appending the position of the current item given by `Menu::index` to
+/// the bottom label, e.g. " (3/7)", if it fits.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn show_menu_with_position<M: Menu>(menu: &M) {
+    display_menu(menu, false, true)
+}
+
+/// Like `show_menu`, but cutting a bottom label that does not fit short in the middle with
+/// `format::ELLIPSIS`, keeping both ends of e.g. a hash, for menus whose events are handled
+/// with `handle_menu_button_event_scrolling`.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn show_menu_scrolling<M: Menu>(menu: &M) {
+    display_menu(menu, true, false)
+}
+
+/// Whether the bottom label of a menu is shown in full.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn menu_label_fits(label: &MenuLabelBottom) -> bool {
+    label.text.len() <= 16 && text_width(label.text, label.bold) <= SCREEN_WIDTH
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn display_menu<M: Menu>(menu: &M, shorten: bool, position: bool) {
     clear_screen();
     let (top, bottom) = menu.label();
+    let (index, count) = menu.index();
+    let mut shortened: ArrayString<16> = ArrayString::new();
+    let bottom = if position && count > 1 {
+        shortened = make_title_buffer(bottom.text, index, count);
+        MenuLabelBottom {
+            text: shortened.as_str(),
+            bold: bottom.bold,
+        }
+    } else if shorten && !menu_label_fits(&bottom) {
+        shorten_to_fit(
+            &mut shortened,
+            bottom.text,
+            bottom.bold,
+            SCREEN_WIDTH,
+            format::TruncateMode::Middle,
+        );
+        MenuLabelBottom {
+            text: shortened.as_str(),
+            bold: bottom.bold,
+        }
+    } else {
+        bottom
+    };
+    display_menu_labels(top, bottom, menu.icon());
+}
+
+/// Write `text` into `buffer` cut short with `format::ELLIPSIS` as given by `mode`, keeping as
+/// much of it as fits in `max_width` pixels, e.g. a row of the screen.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn shorten_to_fit(
+    buffer: &mut ArrayString<16>,
+    text: &str,
+    bold: bool,
+    max_width: usize,
+    mode: format::TruncateMode,
+) {
+    let mut max_chars = 16;
+    loop {
+        write_truncated_within(buffer, text, max_chars, mode);
+        if max_chars <= format::ELLIPSIS.len() || text_width(buffer, bold) <= max_width {
+            break;
+        }
+        max_chars -= 1;
+    }
+}
+
+/// Draw the screen of a menu item with labels `top` and `bottom`, and `icon` on their left.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+fn display_menu_labels(
+    top: MenuLabelTop,
+    bottom: MenuLabelBottom,
+    icon: Option<&ledger_device_sdk::ui::bitmaps::Glyph>,
+) {
+    // With an icon, the labels are centered on the space to its right
+    let layout = |text: &str, bold: bool| match icon {
+        Some(_) => {
+            let space = SCREEN_WIDTH - SIDE_GLYPH_AREA_WIDTH;
+            Layout::Custom(SIDE_GLYPH_AREA_WIDTH + space.saturating_sub(text_width(text, bold)) / 2)
+        }
+        None => Layout::Centered,
+    };
+    if let Some(glyph) = icon {
+        let center = (MENU_TOP_TEXT_Y + MENU_BOTTOM_TEXT_Y + TEXT_ROW_HEIGHT) / 2;
+        Icon::from(glyph)
+            .set_x(SIDE_GLYPH_X)
+            .set_y(center.saturating_sub(glyph.height as usize / 2) as i16)
+            .display();
+    }
     match top {
         MenuLabelTop::Icon(icon) => icon.instant_display(),
         MenuLabelTop::Text(txt) => {
-            txt.place(Location::Custom(MENU_TOP_TEXT_Y), Layout::Centered, true);
+            txt.place(Location::Custom(MENU_TOP_TEXT_Y), layout(txt, true), true);
         }
     }
     bottom.text.place(
         Location::Custom(MENU_BOTTOM_TEXT_Y),
-        Layout::Centered,
+        layout(bottom.text, bottom.bold),
         bottom.bold,
     );
 
@@ -446,20 +3572,653 @@ pub fn show_menu<M: Menu>(menu: &M) {
     RIGHT_ARROW.instant_display();
 }
 
+/// Show the home screen of an app, with `app_name` over its `version`, e.g. "v1.2.0", beside
+/// the dashboard glyph. Either button goes from there to `menu`, e.g. of settings and a quit
+/// item, which is handled as with `handle_menu_button_action` until both buttons are pressed
+/// on one of its items that has a result; that result is returned. To get back to the home
+/// screen, the menu can have an item whose result has the caller show it again.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn show_idle<M: Menu>(app_name: &str, version: &str, menu: &mut M) -> M::BothResult {
+    let mut buttons = ButtonsState::new();
+    let mut home = true;
+    let mut redraw = true;
+    loop {
+        if redraw {
+            if home {
+                clear_screen();
+                display_menu_labels(
+                    MenuLabelTop::Text(app_name),
+                    MenuLabelBottom {
+                        text: version,
+                        bold: false,
+                    },
+                    Some(&bitmaps::DASHBOARD_GLYPH),
+                );
+            } else {
+                show_menu(menu);
+            }
+        }
+        redraw = true;
+        match get_event(&mut buttons) {
+            Some(ButtonEvent::LeftButtonRelease | ButtonEvent::RightButtonRelease) if home => {
+                home = false;
+            }
+            Some(btn) if !home => match handle_menu_button_action(menu, btn) {
+                MenuAction::Selected(result) => break result,
+                MenuAction::Moved => (),
+                MenuAction::None => redraw = false,
+            },
+            Some(_) | None => redraw = false,
+        }
+    }
+}
+
+/// Most options that `select_from` offers.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub const MAX_SELECT_OPTIONS: usize = 16;
+
+/// Let the user pick one of `options`, e.g. a fee level, shown as the items of a `BoundedMenu`
+/// under `title`. Left and right move between the options, going round at the ends, and both
+/// buttons select one, returning its value. After the last option is a cancel item, which
+/// returns `None`, as do an empty list of options and one of more than `MAX_SELECT_OPTIONS`.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn select_from<T: Copy>(title: &str, options: &[(&str, T)]) -> Option<T> {
+    if options.is_empty() || options.len() > MAX_SELECT_OPTIONS {
+        trace!("Cannot select from {} options", options.len());
+        return None;
+    }
+    let mut items: arrayvec::ArrayVec<MenuItem<Option<T>>, { MAX_SELECT_OPTIONS + 1 }> = options
+        .iter()
+        .map(|&(label, value)| MenuItem {
+            top: MenuLabelTop::Text(title),
+            bottom: MenuLabelBottom {
+                text: label,
+                bold: false,
+            },
+            result: Some(Some(value)),
+        })
+        .collect();
+    // The cancel item comes after the options
+    items.push(MenuItem {
+        top: MenuLabelTop::Icon(&BACK_ICON),
+        bottom: MenuLabelBottom {
+            text: strings::strings().cancel,
+            bold: true,
+        },
+        result: Some(None),
+    });
+    let mut menu = BoundedMenu::new(&items, true);
+    let mut buttons = ButtonsState::new();
+    menu.show();
+    loop {
+        if let Some(event) = get_event(&mut buttons) {
+            match menu.handle_button(event) {
+                MenuAction::Selected(result) => break result,
+                MenuAction::Moved => menu.show(),
+                MenuAction::None => (),
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "nanos")]
+const IDLE_DOT_Y: i16 = 30;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[cfg(not(target_os = "nanos"))]
+const IDLE_DOT_Y: i16 = 60;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const IDLE_DOT_MIN_X: i16 = 20;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const IDLE_DOT_MAX_X: i16 = 106;
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+const IDLE_DOT_ICON: Icon = Icon::from(&bitmaps::DOT_GLYPH).set_y(IDLE_DOT_Y);
+
+/// A dot that moves back and forth along the bottom of a menu screen, to show that the app is
+/// alive while idling. It only moves when `tick` is called, e.g. on each ticker event.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub struct IdleAnimation {
+    x: i16,
+    step: i16,
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl IdleAnimation {
+    pub const fn new() -> Self {
+        IdleAnimation {
+            x: IDLE_DOT_MIN_X,
+            step: 1,
+        }
+    }
+
+    pub fn position(&self) -> i16 {
+        self.x
+    }
+
+    /// Draw the dot, e.g. after `show_menu` has cleared the screen.
+    pub fn display(&self) {
+        IDLE_DOT_ICON.set_x(self.x).instant_display();
+    }
+
+    /// Move the dot one pixel and redraw it.
+    pub fn tick(&mut self) {
+        IDLE_DOT_ICON.set_x(self.x).erase();
+        if !(IDLE_DOT_MIN_X..=IDLE_DOT_MAX_X).contains(&(self.x + self.step)) {
+            self.step = -self.step;
+        }
+        self.x += self.step;
+        self.display();
+    }
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+impl Default for IdleAnimation {
+    fn default() -> Self {
+        IdleAnimation::new()
+    }
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
 #[inline(never)]
 pub fn handle_menu_button_event<M: Menu>(
     menu: &mut M,
     btn: ButtonEvent,
 ) -> Option<<M as Menu>::BothResult> {
+    match handle_menu_button_action(menu, btn) {
+        MenuAction::Selected(result) => Some(result),
+        MenuAction::Moved | MenuAction::None => None,
+    }
+}
+
+/// What a button event did to a menu, as returned by `handle_menu_button_action`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuAction<T> {
+    /// Nothing changed, so the menu need not be shown again.
+    None,
+    /// The menu moved to another item, or both buttons were pressed on an item without a
+    /// result, and it needs to be shown again.
+    Moved,
+    /// Both buttons were pressed on an item with this result.
+    Selected(T),
+}
+
+/// Like `handle_menu_button_event`, but also telling whether the menu needs to be shown again.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn handle_menu_button_action<M: Menu>(
+    menu: &mut M,
+    btn: ButtonEvent,
+) -> MenuAction<<M as Menu>::BothResult> {
     match btn {
         ButtonEvent::LeftButtonRelease => {
             menu.move_left();
+            MenuAction::Moved
         }
         ButtonEvent::RightButtonRelease => {
             menu.move_right();
+            MenuAction::Moved
+        }
+        ButtonEvent::BothButtonsRelease => match menu.handle_both() {
+            Some(result) => MenuAction::Selected(result),
+            None => MenuAction::Moved,
+        },
+        _ => MenuAction::None,
+    }
+}
+
+/// Like `handle_menu_button_event`, for menus shown with `show_menu_scrolling`. When the bottom
+/// label of the current item does not fit, both buttons first page through it in a scroller,
+/// and the item is only selected if the user accepts from its last page. Otherwise the menu is
+/// shown again.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+pub fn handle_menu_button_event_scrolling<M: Menu>(
+    menu: &mut M,
+    btn: ButtonEvent,
+) -> Option<<M as Menu>::BothResult> {
+    if let ButtonEvent::BothButtonsRelease = btn {
+        let (top, bottom) = menu.label();
+        if !menu_label_fits(&bottom) {
+            let title = match top {
+                MenuLabelTop::Text(text) => text,
+                MenuLabelTop::Icon(_) => "",
+            };
+            let outcome = WriteScroller::<_, 16>::new(title, |w| Ok(w.write_str(bottom.text)?))
+                .with_word_wrap(true)
+                .with_show_index(true)
+                .run();
+            if outcome != PromptOutcome::Accepted {
+                show_menu_scrolling(menu);
+                return None;
+            }
+        }
+    }
+    handle_menu_button_event(menu, btn)
+}
+
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn page_count_of_full_rows() {
+        assert_eq!(page_count(0, 16, 1), 1);
+        assert_eq!(page_count(1, 16, 1), 1);
+        assert_eq!(page_count(16, 16, 1), 1);
+        assert_eq!(page_count(17, 16, 1), 2);
+        assert_eq!(page_count(48, 16, 3), 1);
+        assert_eq!(page_count(49, 16, 3), 2);
+        const PAGES: usize = page_count(100, 16, 1);
+        assert_eq!(PAGES, 7);
+    }
+
+    #[test]
+    fn two_byte_chars_page_by_the_chars_that_fit() {
+        let text = "é".repeat(40);
+        let scroller = WriteScroller::<_, 16>::new("Name", |w| Ok(w.write_str(&text)?));
+        assert!(matches!(scroller.page_count(), Ok(5)));
+        let pages: Vec<_> = scroller
+            .pages()
+            .ok()
+            .unwrap()
+            .map(|page| page.ok())
+            .collect();
+        assert_eq!(pages.len(), 5);
+        assert!(pages.iter().all(|page| page.as_deref() == Some("éééééééé")));
+    }
+
+    #[test]
+    fn nothing_is_kept_after_a_cut_char() {
+        let mut buffer: ArrayString<16> = ArrayString::new();
+        let mut w = mk_prompt_write(&mut buffer);
+        // The 'é' is cut by the end of the buffer, so the 'b' after it is not kept either
+        let (name, suffix) = ("aaaaaaaaaaaaaaaé", 'b');
+        write!(w, "{}{}", name, suffix).unwrap();
+        assert_eq!(w.total(), 18);
+        assert_eq!(w.remaining(), 0);
+        assert_eq!(buffer.as_str(), "aaaaaaaaaaaaaaa");
+    }
+
+    #[test]
+    fn percent_seen_at_each_page() {
+        let seen: Vec<_> = (0..4).map(|page| percent_seen(page, 4)).collect();
+        assert_eq!(seen, [25, 50, 75, 100]);
+        let seen: Vec<_> = (0..3).map(|page| percent_seen(page, 3)).collect();
+        assert_eq!(seen, [33, 66, 100]);
+        assert_eq!(percent_seen(0, 1), 100);
+        assert_eq!(percent_seen(0, 0), 100);
+        assert_eq!(percent_seen(6, 7), 100);
+    }
+
+    #[test]
+    fn percent_title() {
+        assert_eq!(
+            make_percent_title_buffer("Amount", 1, 4).as_str(),
+            "Amount (50%)"
+        );
+        assert_eq!(
+            make_percent_title_buffer("Amount", 3, 4).as_str(),
+            "Amount (100%)"
+        );
+        // No room for the percentage, or only one page
+        assert_eq!(
+            make_percent_title_buffer("Receive address", 0, 4).as_str(),
+            "Receive address"
+        );
+        assert_eq!(make_percent_title_buffer("Amount", 0, 1).as_str(), "Amount");
+    }
+
+    fn menu_items() -> [MenuItem<'static, u8>; 3] {
+        let item = |text, result| MenuItem {
+            top: MenuLabelTop::Text(text),
+            bottom: MenuLabelBottom {
+                text: "",
+                bold: false,
+            },
+            result,
+        };
+        [
+            item("One", Some(1)),
+            item("Two", Some(2)),
+            item("Back", None),
+        ]
+    }
+
+    #[test]
+    fn bounded_menu_wraps_round() {
+        let items = menu_items();
+        let mut menu = BoundedMenu::new(&items, true);
+        menu.move_left();
+        assert_eq!(menu.index(), (2, 3));
+        assert_eq!(menu.handle_both(), None);
+        menu.move_right();
+        assert_eq!(menu.index(), (0, 3));
+        assert_eq!(menu.handle_both(), Some(1));
+    }
+
+    #[test]
+    fn bounded_menu_stops_at_the_ends() {
+        let items = menu_items();
+        let mut menu = BoundedMenu::new(&items, false);
+        menu.move_left();
+        assert_eq!(menu.index(), (0, 3));
+        for _ in 0..4 {
+            menu.move_right();
+        }
+        assert_eq!(menu.index(), (2, 3));
+        menu.move_left();
+        assert_eq!(menu.handle_both(), Some(2));
+        let mut empty = BoundedMenu::<u8>::new(&[], true);
+        empty.move_left();
+        empty.move_right();
+        assert_eq!(empty.index(), (0, 0));
+        assert_eq!(empty.handle_both(), None);
+    }
+
+    #[test]
+    fn bounded_menu_over_borrowed_labels() {
+        let label = std::string::String::from("Medium");
+        let items = [MenuItem {
+            top: MenuLabelTop::Text(&label[..3]),
+            bottom: MenuLabelBottom {
+                text: &label,
+                bold: false,
+            },
+            result: Some(2),
+        }];
+        let mut menu = BoundedMenu::new(&items, true);
+        assert!(matches!(
+            menu.handle_button(ButtonEvent::RightButtonRelease),
+            MenuAction::Moved
+        ));
+        let (top, bottom) = menu.labels();
+        assert!(matches!(top, MenuLabelTop::Text("Med")));
+        assert_eq!(bottom.text, "Medium");
+        assert!(matches!(
+            menu.handle_button(ButtonEvent::BothButtonsRelease),
+            MenuAction::Selected(2)
+        ));
+    }
+
+    /// The offset and length of each page of `scroller`, as from `page_spans`.
+    fn spans<S: PromptSource<16>>(scroller: &WriteScroller<'_, S, 16>) -> Vec<(usize, usize)> {
+        let count = scroller.page_spans(0).ok().unwrap().0;
+        let spans: Vec<_> = (0..count)
+            .map(|page| scroller.page_spans(page).ok().unwrap().1.unwrap())
+            .collect();
+        assert!(matches!(scroller.page_spans(count), Ok((c, None)) if c == count));
+        spans
+    }
+
+    #[test]
+    fn page_spans_wrap_at_words() {
+        let scroller = WriteScroller::<_, 16>::new("Memo", |w| {
+            Ok(w.write_str("the quick brown fox jumps over the lazy dog")?)
+        })
+        .with_word_wrap(true);
+        assert_eq!(spans(&scroller), [(0, 16), (16, 15), (31, 12)]);
+        // A word longer than a page is cut
+        let scroller =
+            WriteScroller::<_, 16>::new("Memo", |w| Ok(w.write_str("abcdefghijklmnopqrstuvwxyz")?))
+                .with_word_wrap(true);
+        assert_eq!(spans(&scroller), [(0, 16), (16, 10)]);
+    }
+
+    #[test]
+    fn page_spans_end_at_line_breaks() {
+        let scroller =
+            WriteScroller::<_, 16>::new("Memo", |w| Ok(w.write_str("ab\n\n0123456789abcdefgh")?));
+        assert_eq!(spans(&scroller), [(0, 2), (3, 0), (4, 16), (20, 2)]);
+        // Chars are not cut between pages
+        let scroller = WriteScroller::<_, 16>::new("Name", |w| Ok(w.write_str("aéééééééé")?));
+        assert_eq!(spans(&scroller), [(0, 15), (15, 2)]);
+    }
+
+    #[test]
+    fn page_for_offset_follows_line_breaks() {
+        let scroller = WriteScroller::<_, 16>::new("Memo", |w| Ok(w.write_str("ab\ncdé\nf")?));
+        assert!(matches!(scroller.page_for_offset(0), Ok(0)));
+        assert!(matches!(scroller.page_for_offset(3), Ok(1)));
+        assert!(matches!(scroller.page_for_offset(7), Ok(1)));
+        assert!(matches!(scroller.page_for_offset(8), Ok(2)));
+        assert!(matches!(scroller.page_for_offset(100), Ok(2)));
+        let reversed = scroller.with_reverse(true);
+        assert!(matches!(reversed.page_for_offset(0), Ok(2)));
+    }
+
+    #[test]
+    fn known_size_keeps_line_breaks() {
+        let scroller = WriteScroller::<_, 16>::new("Memo", |w| Ok(w.write_str("ab\ncdé\nf")?));
+        let size = scroller.content_size().ok().unwrap();
+        assert_eq!(
+            size,
+            ContentSize {
+                bytes: 9,
+                chars: 8,
+                newlines: 2
+            }
+        );
+        let pages = |scroller: &WriteScroller<_, 16>| -> Vec<_> {
+            scroller
+                .pages()
+                .ok()
+                .unwrap()
+                .map(|page| page.ok())
+                .collect()
+        };
+        let expected = pages(&scroller);
+        assert_eq!(expected.len(), 3);
+        assert_eq!(pages(&scroller.with_known_size(size)), expected);
+    }
+
+    #[test]
+    fn page_for_offset_follows_word_wrap() {
+        let text = "one two three four five";
+        let scroller =
+            WriteScroller::<_, 16>::new("Memo", |w| Ok(w.write_str(text)?)).with_word_wrap(true);
+        // "one two three " then "four five"
+        assert!(matches!(scroller.page_for_offset(13), Ok(0)));
+        assert!(matches!(scroller.page_for_offset(14), Ok(1)));
+    }
+
+    #[test]
+    fn page_for_offset_three_rows_skips_the_subtitle() {
+        let text = "Subtitle\naaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbcccccccccccccccc";
+        let scroller = WriteScroller::<_, 16>::new("Memo", |w| Ok(w.write_str(text)?))
+            .with_first_line_as_subtitle(true);
+        // Two rows of the body per page below the subtitle
+        assert!(matches!(scroller.page_for_offset_three_rows(3), Ok(0)));
+        assert!(matches!(scroller.page_for_offset_three_rows(9 + 31), Ok(0)));
+        assert!(matches!(scroller.page_for_offset_three_rows(9 + 32), Ok(1)));
+    }
+
+    /// The letters of the alphabet, written from the offset asked for.
+    struct Alphabet;
+
+    impl PromptSource<16> for Alphabet {
+        fn write_page(
+            &self,
+            offset: usize,
+            out: &mut PromptWrite<'_, 16>,
+        ) -> Result<(), ScrollerError> {
+            for c in (b'a'..=b'z').skip(offset) {
+                out.write_char(c as char)?;
+            }
+            Ok(())
         }
-        ButtonEvent::BothButtonsRelease => return menu.handle_both(),
-        _ => (),
     }
-    None
+
+    #[test]
+    fn source_seeking_to_the_offset() {
+        let scroller = WriteScroller::from_source("Letters", Alphabet);
+        let pages: Vec<_> = scroller
+            .pages()
+            .ok()
+            .unwrap()
+            .map(|page| page.ok())
+            .collect();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].as_deref(), Some("abcdefghijklmnop"));
+        assert_eq!(pages[1].as_deref(), Some("qrstuvwxyz"));
+    }
+
+    #[test]
+    fn lines_seeking_to_the_offset() {
+        let lines: &[&str] = &["memo one", "a much longer second line", "x"];
+        let scroller = WriteScroller::<_, 16>::from_lines("Memo", lines);
+        let pages: Vec<_> = scroller
+            .pages()
+            .ok()
+            .unwrap()
+            .map(|page| page.ok())
+            .collect();
+        let expected = ["memo one", "a much longer se", "cond line", "x"];
+        assert_eq!(pages.len(), expected.len());
+        for (page, expected) in pages.iter().zip(expected) {
+            assert_eq!(page.as_deref(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn titles_fit_the_buffer_and_the_screen() {
+        let narrow = MonospaceFont { cell_width: 8 };
+        let wide = MonospaceFont { cell_width: 9 };
+        assert!(title_fits_with(&narrow, "Receive address:"));
+        assert!(!title_fits_with(&wide, "Receive address:"));
+        // 16 chars, but too long for the title buffer
+        assert!(!title_fits_with(&narrow, "Détails du dépôt"));
+        assert!(!title_fits("A title too long to fit"));
+    }
+
+    /// The device font, but with the private use char U+E000 drawn as a wide icon.
+    struct WithIcon;
+
+    impl FontMetrics for WithIcon {
+        fn advance(&self, c: char, bold: bool) -> usize {
+            match c {
+                '\u{E000}' => 40,
+                c => DeviceFont.advance(c, bold),
+            }
+        }
+    }
+
+    #[test]
+    fn font_metrics_set_the_text_width() {
+        static METRICS: &(dyn FontMetrics + Sync) = &WithIcon;
+        // No other test draws U+E000, so its width changing does not affect them
+        let text = "\u{E000}\u{E000}\u{E000}\u{E000}";
+        assert_ne!(text_width(text, false), 160);
+        set_font_metrics(&METRICS);
+        assert_eq!(text_width(text, false), 160);
+        assert!(!title_fits(text));
+        assert_eq!(text_width("ab", false), DeviceFont.text_width("ab", false));
+    }
+
+    #[test]
+    fn monospace_text_takes_its_char_count_in_cells() {
+        let font = MonospaceFont { cell_width: 7 };
+        assert_eq!(font.text_width("0x1f", false), 28);
+        assert_eq!(
+            font.text_width("WWww", true),
+            font.text_width("iiII", false)
+        );
+        assert_eq!(font.text_width("é", false), 7);
+    }
+
+    #[test]
+    fn each_error_has_its_message() {
+        let message = |err: ScrollerError| {
+            let mut buffer: ArrayString<16> = ArrayString::new();
+            write!(mk_prompt_write(&mut buffer), "{}", err).unwrap();
+            buffer
+        };
+        assert_eq!(message(ScrollerError::Format).as_str(), "Display failed");
+        assert_eq!(message(ScrollerError::Utf8).as_str(), "Invalid UTF-8");
+        assert_eq!(
+            message(ScrollerError::TooManyPages).as_str(),
+            "Too many pages"
+        );
+        assert_eq!(
+            message(ScrollerError::Overflow).as_str(),
+            "Too much content"
+        );
+        assert_eq!(ScrollerError::from(core::fmt::Error), ScrollerError::Format);
+        // Half of a two-byte char
+        let not_utf8 = core::str::from_utf8(&"é".as_bytes()[..1]).unwrap_err();
+        assert_eq!(ScrollerError::from(not_utf8), ScrollerError::Utf8);
+    }
+
+    #[test]
+    fn counter_suffix_fits_thousands_of_pages() {
+        assert_eq!(counter_suffix_len(9, 0), " (9/9)".len());
+        assert_eq!(counter_suffix_len(9, 2), " (09/09)".len());
+        assert_eq!(counter_suffix_len(1234, 0), " (1234/1234)".len());
+        assert_eq!(counter_suffix_len(9999, 2), " (9999/9999)".len());
+        assert_eq!(counter_suffix_len(10000, 0), " (10000/10000)".len());
+    }
+
+    #[test]
+    fn page_spans_end_at_the_page_break_char() {
+        let scroller = WriteScroller::<_, 16>::new("Path", |w| Ok(w.write_str("ab;;cd;ef")?))
+            .with_page_break_char(';');
+        assert_eq!(spans(&scroller), [(0, 2), (3, 0), (4, 2), (7, 2)]);
+    }
+
+    #[test]
+    fn break_interval_is_where_pages_wrap() {
+        let hash = "0123456789abcdef0123456789abcdef01234567";
+        let scroller = WriteScroller::<_, 16>::new("Hash", |w| Ok(w.write_str(hash)?))
+            .with_word_wrap(true)
+            .with_break_interval(8);
+        assert_eq!(spans(&scroller), [(0, 16), (16, 16), (32, 8)]);
+        // Groups are counted from the start of the word they are in
+        let scroller = WriteScroller::<_, 16>::new("Hash", |w| Ok(write!(w, "Hash: {}", hash)?))
+            .with_word_wrap(true)
+            .with_break_interval(8);
+        assert_eq!(spans(&scroller), [(0, 14), (14, 16), (30, 16)]);
+        // Without word wrap, pages only end between groups, and the rest fits on the last
+        let scroller =
+            WriteScroller::<_, 16>::new("Hash", |w| Ok(w.write_str(hash)?)).with_break_interval(6);
+        assert_eq!(spans(&scroller), [(0, 12), (12, 12), (24, 16)]);
+    }
+
+    #[test]
+    fn hex_pages_eight_bytes_at_a_time() {
+        let bytes: Vec<u8> = (0..20).collect();
+        for (reverse, expected) in [
+            (false, ["0001020304050607", "08090a0b0c0d0e0f", "10111213"]),
+            (true, ["131211100f0e0d0c", "0b0a090807060504", "03020100"]),
+        ] {
+            let scroller =
+                WriteScroller::<_, 16>::new("Hash", |w| Ok(format::write_hex(w, &bytes, reverse)?));
+            let pages: Vec<_> = scroller
+                .pages()
+                .ok()
+                .unwrap()
+                .map(|page| page.ok())
+                .collect();
+            assert_eq!(pages.len(), expected.len());
+            for (page, expected) in pages.iter().zip(expected) {
+                assert_eq!(page.as_deref(), Some(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn truncated_titles_fit_their_bytes() {
+        let title = title_buffer_for("Détails du dépôt");
+        // 16 chars, in 19 bytes
+        assert_eq!(title.as_str(), "Détai...dépôt");
+        let mut buffer = ArrayString::new();
+        shorten_to_fit(
+            &mut buffer,
+            "Détails du dépôt",
+            false,
+            usize::MAX,
+            format::TruncateMode::Tail,
+        );
+        assert_eq!(buffer.as_str(), "...ls du dépôt");
+    }
 }
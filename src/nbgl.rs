@@ -0,0 +1,113 @@
+//! Prompts for Stax and Flex, which draw with NBGL rather than BAGL. The content of a scroller
+//! is written out in full and handed to an NBGL review, which pages it itself.
+
+use crate::strings::strings;
+use crate::*;
+use ledger_device_sdk::nbgl::{Field, NbglChoice, NbglReview};
+
+/// Most bytes of content shown by one scroller. Longer content is rejected rather than shown
+/// in part.
+pub const MAX_CONTENT_LEN: usize = 512;
+
+/// Review `title` with the text written by `prompt_function` as an NBGL review of one field.
+/// There are no rows to choose from, so `show_index` and the three-row layout are ignored.
+fn review_content<
+    F: for<'b> Fn(&mut PromptWrite<'b, MAX_CONTENT_LEN>) -> Result<(), ScrollerError>,
+>(
+    title: &str,
+    prompt_function: F,
+) -> PromptOutcome {
+    let mut buffer: ArrayString<MAX_CONTENT_LEN> = ArrayString::new();
+    let mut prompt_write = mk_prompt_write(&mut buffer);
+    if let Err(err) = prompt_function(&mut prompt_write) {
+        trace!("Content failed to write");
+        return PromptOutcome::Errored(err);
+    }
+    if prompt_write.total > MAX_CONTENT_LEN {
+        trace!("Content too long");
+        return PromptOutcome::Errored(ScrollerError::Overflow);
+    }
+    let fields = [Field {
+        name: title,
+        value: buffer.as_str(),
+    }];
+    if NbglReview::new()
+        .titles(title, "", strings().approve)
+        .show(&fields)
+    {
+        PromptOutcome::Accepted
+    } else {
+        trace!("User rejected prompt");
+        PromptOutcome::Rejected
+    }
+}
+
+#[inline(never)]
+pub fn write_scroller_outcome<
+    F: for<'b> Fn(&mut PromptWrite<'b, 16>) -> Result<(), ScrollerError>,
+>(
+    _show_index: bool,
+    title: &str,
+    prompt_function: F,
+) -> PromptOutcome {
+    review_content(title, |w| write_pages(w, &prompt_function))
+}
+
+#[inline(never)]
+pub fn write_scroller_three_rows_outcome<
+    F: for<'b> Fn(&mut PromptWrite<'b, 16>) -> Result<(), ScrollerError>,
+>(
+    _show_index: bool,
+    title: &str,
+    prompt_function: F,
+) -> PromptOutcome {
+    review_content(title, |w| write_pages(w, &prompt_function))
+}
+
+/// Write the content of `prompt_function`, which writes a page of 16 bytes at a time, to `w`
+/// page after page.
+fn write_pages<F: for<'b> Fn(&mut PromptWrite<'b, 16>) -> Result<(), ScrollerError>>(
+    w: &mut PromptWrite<'_, MAX_CONTENT_LEN>,
+    prompt_function: &F,
+) -> Result<(), ScrollerError> {
+    let mut offset = 0;
+    loop {
+        let mut page: ArrayString<16> = ArrayString::new();
+        let mut prompt_write = PromptWrite {
+            offset,
+            buffer: &mut page,
+            total: 0,
+            chars: 0,
+            newlines: 0,
+            overflow: &mut [],
+            full: false,
+        };
+        prompt_function(&mut prompt_write)?;
+        let total = prompt_write.total;
+        w.write_str(page.as_str())?;
+        offset += page.len();
+        if offset >= total || page.is_empty() {
+            return Ok(());
+        }
+    }
+}
+
+#[inline(never)]
+pub fn final_accept_prompt_with(
+    prompt: &[&str],
+    confirm_label: &str,
+    reject_label: &str,
+) -> Option<()> {
+    let (message, sub_message) = match prompt {
+        [] => ("", ""),
+        [message] => (*message, ""),
+        [message, sub_message, ..] => (*message, *sub_message),
+    };
+    if !NbglChoice::new().show(message, sub_message, confirm_label, reject_label) {
+        trace!("User rejected at end\n");
+        None
+    } else {
+        trace!("User accepted");
+        Some(())
+    }
+}
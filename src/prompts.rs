@@ -0,0 +1,314 @@
+//! Ready-made prompts for common review flows, built on `write_scroller`.
+
+use crate::format::*;
+use crate::strings::strings;
+use crate::*;
+use core::fmt::Write;
+
+/// Show a fee for confirmation, preceded by a warning screen if it is above `warn_above`.
+pub fn confirm_fee(fee: u128, decimals: u8, symbol: &str, warn_above: u128) -> Option<()> {
+    if fee > warn_above {
+        trace!("Fee above warning threshold: {}", fee);
+        warning_prompt(strings().high_fee)?;
+    }
+    write_scroller(false, strings().fee, |w| {
+        Ok(write_amount(w, fee, decimals, symbol)?)
+    })
+}
+
+/// One entry of a list reviewed with `review_list`.
+pub struct ListItem<'a> {
+    pub label: &'a str,
+    pub amount: u128,
+}
+
+/// Review `items` one at a time, each together with the running total up to and including it,
+/// then confirm the grand total. Rejects if the total overflows.
+pub fn review_list(title: &str, items: &[ListItem], decimals: u8, symbol: &str) -> Option<()> {
+    let mut total: u128 = 0;
+    for item in items {
+        total = match total.checked_add(item.amount) {
+            Some(total) => total,
+            None => {
+                trace!("List total overflows");
+                return None;
+            }
+        };
+        let sections = [
+            Section {
+                header: item.label,
+                contents: &|w| Ok(write_amount(w, item.amount, decimals, symbol)?),
+            },
+            Section {
+                header: strings().running_total,
+                contents: &|w| Ok(write_amount(w, total, decimals, symbol)?),
+            },
+        ];
+        write_sections(false, title, &sections, false)?;
+    }
+    write_scroller(false, strings().total, |w| {
+        Ok(write_amount(w, total, decimals, symbol)?)
+    })
+}
+
+/// Show a nonce or sequence number for confirmation, along with the `expected` value if one is
+/// known. When the two differ, a warning screen is shown first.
+pub fn confirm_nonce(title: &str, actual: u64, expected: Option<u64>) -> Option<()> {
+    match expected {
+        Some(expected) if expected != actual => {
+            trace!("Nonce {} differs from expected {}", actual, expected);
+            warning_prompt(strings().unexpected_nonce)?;
+        }
+        _ => (),
+    }
+    write_scroller(false, title, |w| {
+        write!(w, "{}", actual)?;
+        if let Some(expected) = expected {
+            write!(w, " ({} {})", strings().expected, expected)?;
+        }
+        Ok(())
+    })
+}
+
+/// Show `value`, entered by the user elsewhere, and ask them to confirm that it is the value
+/// they entered.
+pub fn confirm_value_match(title: &str, value: &str) -> Decision {
+    if write_scroller(true, title, |w| Ok(w.write_str(value)?)).is_none() {
+        return Decision::Reject;
+    }
+    let strings = strings();
+    Decision::from(
+        MessageValidator::new(
+            &[strings.value_match_question],
+            &[strings.value_match_confirm],
+            &[strings.value_match_reject],
+        )
+        .ask(),
+    )
+}
+
+/// Show `bytes` encoded in base64 in a scroller.
+pub fn write_scroller_base64(title: &str, bytes: &[u8]) -> Option<()> {
+    write_scroller(true, title, |w| Ok(write_base64(w, bytes)?))
+}
+
+/// Ask to accept or reject `prompt`, with the `default` choice shown in bold. The left button
+/// rejects, the right button accepts, and both buttons take the default.
+pub fn confirm_with_default(prompt: &str, default: Decision) -> Decision {
+    let strings = strings();
+    ask_left_right(prompt, strings.reject, strings.accept, Some(default))
+}
+
+/// Ask `question`, e.g. whether to turn a setting on, where both answers are values for the
+/// app rather than approving or rejecting a transaction. The left button answers No and the
+/// right button answers Yes, returning `true`; pressing both buttons does nothing.
+pub fn ask_yes_no(question: &str) -> bool {
+    let strings = strings();
+    ask_left_right(question, strings.no, strings.yes, None) == Decision::Accept
+}
+
+/// Show `prompt` over `reject_label` on the left and `accept_label` on the right, and wait for
+/// the left button to reject or the right button to accept. Both buttons take `default`, if
+/// any, whose label is shown in bold.
+#[inline(never)]
+fn ask_left_right(
+    prompt: &str,
+    reject_label: &str,
+    accept_label: &str,
+    default: Option<Decision>,
+) -> Decision {
+    let mut buttons = ButtonsState::new();
+    clear_screen();
+    prompt.place(Location::Top, Layout::Centered, false);
+    reject_label.place(
+        Location::Custom(MENU_BOTTOM_TEXT_Y),
+        Layout::Custom(10),
+        default == Some(Decision::Reject),
+    );
+    accept_label.place(
+        Location::Custom(MENU_BOTTOM_TEXT_Y),
+        Layout::RightAligned,
+        default == Some(Decision::Accept),
+    );
+    LEFT_ARROW.instant_display();
+    RIGHT_ARROW.instant_display();
+    loop {
+        match get_event(&mut buttons) {
+            Some(ButtonEvent::LeftButtonRelease) => break Decision::Reject,
+            Some(ButtonEvent::RightButtonRelease) => break Decision::Accept,
+            Some(ButtonEvent::BothButtonsRelease) => {
+                if let Some(default) = default {
+                    break default;
+                }
+            }
+            Some(ButtonEvent::LeftButtonPress) => LEFT_S_ARROW.instant_display(),
+            Some(ButtonEvent::RightButtonPress) => RIGHT_S_ARROW.instant_display(),
+            Some(_) | None => (),
+        }
+    }
+}
+
+/// Page through terms of use in `text`, then ask to accept or reject them. The scroller can only
+/// be left forwards from its last page, so the choice is offered only once all of the text has
+/// been shown; rejecting in the scroller rejects the terms.
+pub fn accept_terms(title: &str, text: &str) -> Decision {
+    if write_scroller_three_rows(true, title, |w| Ok(w.write_str(text)?)).is_none() {
+        return Decision::Reject;
+    }
+    let strings = strings();
+    Decision::from(MessageValidator::new(&[title], &[strings.accept], &[strings.reject]).ask())
+}
+
+/// Review a contract call: the name of the `method`, then each of its decoded `params` as a
+/// name and value, then a final confirmation.
+pub fn confirm_call(method: &str, params: &[(&str, &dyn core::fmt::Display)]) -> Option<()> {
+    let strings = strings();
+    write_scroller(false, strings.method, |w| Ok(w.write_str(method)?))?;
+    for (name, value) in params {
+        write_scroller(true, name, |w| Ok(write!(w, "{}", value)?))?;
+    }
+    final_accept_prompt(&[strings.confirm_call])
+}
+
+/// Show a message to sign, paged as text if it is valid UTF-8. Otherwise a warning is shown
+/// first and the message is paged as hex instead.
+pub fn confirm_message(title: &str, msg: &[u8]) -> Option<()> {
+    match core::str::from_utf8(msg) {
+        Ok(text) => write_scroller_three_rows(true, title, |w| Ok(w.write_str(text)?)),
+        Err(_) => {
+            trace!("Message is not UTF-8, showing it as hex");
+            warning_prompt(strings().not_text)?;
+            write_scroller_three_rows(true, title, |w| Ok(write_hex(w, msg, false)?))
+        }
+    }
+}
+
+/// Review a signing request from a dapp or other app: first the `origin` it comes from, then a
+/// `summary` of what is signed, then ask to accept or reject it.
+pub fn confirm_signing(origin: &str, summary: &str) -> Decision {
+    let strings = strings();
+    if write_scroller(true, strings.from, |w| Ok(w.write_str(origin)?)).is_none()
+        || write_scroller_three_rows(true, strings.sign_request, |w| Ok(w.write_str(summary)?))
+            .is_none()
+    {
+        return Decision::Reject;
+    }
+    Decision::from(
+        MessageValidator::new(
+            &[strings.sign_request],
+            &[strings.accept],
+            &[strings.reject],
+        )
+        .ask(),
+    )
+}
+
+/// Review the `scopes` an app asks to be granted, each on its own pages, then approve or deny
+/// all of them at once.
+pub fn approve_scopes(title: &str, scopes: &[&str]) -> Option<()> {
+    for scope in scopes {
+        write_scroller(false, title, |w| Ok(w.write_str(scope)?))?;
+    }
+    final_accept_prompt(&[strings().grant_access])
+}
+
+/// Like `approve_scopes`, but asking after each scope whether to grant it, and recording the
+/// answer next to it in `scopes`. The final prompt approves the selection as a whole.
+pub fn approve_scopes_partial(title: &str, scopes: &mut [(&str, bool)]) -> Option<()> {
+    let strings = strings();
+    for (scope, granted) in scopes.iter_mut() {
+        write_scroller(false, title, |w| Ok(w.write_str(scope)?))?;
+        *granted = confirm_with_default(strings.grant_scope, Decision::Accept) == Decision::Accept;
+    }
+    final_accept_prompt(&[strings.grant_access])
+}
+
+/// Review nested data given as `(depth, key, value)` items in order, each on its own page and
+/// indented by its depth, e.g. the fields of a JSON object.
+pub fn review_tree(title: &str, items: &[(usize, &str, &str)]) -> Option<()> {
+    let scroller = WriteScroller::<_, 16>::new(title, |w| {
+        for (i, (depth, key, value)) in items.iter().enumerate() {
+            if i > 0 {
+                w.write_str("\n")?;
+            }
+            write_indented(w, *depth, key, value)?;
+        }
+        Ok(())
+    })
+    .with_page_break_char('\n')
+    .with_show_index(true);
+    scroller.run().accepted()
+}
+
+/// Show the parameters of a vesting or time lock: when it unlocks, as a Unix timestamp, and the
+/// amount it holds, on the same pages.
+pub fn confirm_vesting(unlocks: i64, amount: u128, decimals: u8, symbol: &str) -> Option<()> {
+    let strings = strings();
+    let sections = [
+        Section {
+            header: strings.unlocks,
+            contents: &|w| Ok(write_timestamp(w, unlocks)?),
+        },
+        Section {
+            header: strings.amount,
+            contents: &|w| Ok(write_amount(w, amount, decimals, symbol)?),
+        },
+    ];
+    write_sections(false, strings.vesting, &sections, false)
+}
+
+/// Confirm an address that a human-readable `name` resolves to, e.g. an ENS name. The name is
+/// shown first over the address cut short in the middle, then the full address is paged, and
+/// it can only be accepted from its last page.
+pub fn confirm_named_address(name: &str, address: &str) -> Option<()> {
+    write_scroller(false, name, |w| {
+        Ok(write_truncated(w, address, 16, TruncateMode::Middle)?)
+    })?;
+    write_scroller_three_rows(true, strings().address, |w| Ok(w.write_str(address)?))
+}
+
+/// Review a transfer of `amount` from the address `from` to the address `to`, then confirm it.
+/// With `warn_if_same`, a warning is shown first when the two addresses are equal, as when
+/// sending to oneself by mistake. Addresses are compared exactly, so ones with several
+/// spellings should be normalized first.
+pub fn confirm_transfer(
+    from: &str,
+    to: &str,
+    amount: u128,
+    decimals: u8,
+    symbol: &str,
+    warn_if_same: bool,
+) -> Option<()> {
+    let strings = strings();
+    if warn_if_same && from == to {
+        trace!("Transfer from an address to itself");
+        warning_prompt(strings.same_address)?;
+    }
+    write_scroller(true, strings.from, |w| Ok(w.write_str(from)?))?;
+    write_scroller(true, strings.to, |w| Ok(w.write_str(to)?))?;
+    write_scroller(false, strings.amount, |w| {
+        Ok(write_amount(w, amount, decimals, symbol)?)
+    })?;
+    final_accept_prompt(&[strings.confirm_transfer])
+}
+
+/// Warn that a transaction is signed without being shown in full, as when an app cannot parse
+/// it, and ask to accept the risk. Call this before reviewing what could be shown of it; it
+/// returns like `final_accept_prompt`.
+pub fn blind_signing_warning() -> Option<()> {
+    let strings = strings();
+    warning_prompt(strings.blind_signing)?;
+    if !MessageValidator::new(
+        &[strings.proceed_with_caution],
+        &[strings.accept],
+        &[strings.reject],
+    )
+    .ask()
+    {
+        trace!("User rejected blind signing");
+        None
+    } else {
+        trace!("User accepted blind signing");
+        Some(())
+    }
+}
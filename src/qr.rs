@@ -0,0 +1,578 @@
+//! QR codes, to let an address or other value be scanned from the screen.
+//!
+//! Only byte mode, error correction level L and versions 1 to 5 are supported. That is enough
+//! for up to 106 bytes, e.g. any common address, while keeping the code small enough for the
+//! screen of the devices this is built for.
+
+use crate::*;
+
+/// Highest QR code version that can be encoded.
+pub const MAX_VERSION: usize = 5;
+const MAX_SIZE: usize = 17 + 4 * MAX_VERSION;
+
+/// Data and error correction codewords of each version at level L. All of these versions use a
+/// single block, so the codewords don't need to be interleaved.
+const DATA_CODEWORDS: [usize; MAX_VERSION] = [19, 34, 55, 80, 108];
+const EC_CODEWORDS: [usize; MAX_VERSION] = [7, 10, 15, 20, 26];
+const MAX_CODEWORDS: usize = 108 + 26;
+
+/// Width in modules of the light border drawn around a code. The standard asks for 4, which
+/// doesn't leave room for a useful scale on these screens; 2 is enough for common readers.
+pub const QUIET_ZONE: usize = 2;
+
+/// The modules of an encoded QR code, one bit per module in each row.
+pub struct QrCode {
+    size: usize,
+    modules: [u64; MAX_SIZE],
+}
+
+impl QrCode {
+    /// Encode `data` in the smallest version that fits, or `None` if it is longer than
+    /// version `MAX_VERSION` allows.
+    pub fn encode(data: &[u8]) -> Option<QrCode> {
+        // Mode indicator and length take 12 bits, the terminator 4
+        let version = (1..=MAX_VERSION).find(|v| data.len() + 2 <= DATA_CODEWORDS[v - 1])?;
+        let data_len = DATA_CODEWORDS[version - 1];
+        let ec_len = EC_CODEWORDS[version - 1];
+
+        // The 4-bit byte mode indicator shifts everything after it by half a byte
+        let mut codewords = [0u8; MAX_CODEWORDS];
+        codewords[0] = 0x40 | (data.len() >> 4) as u8;
+        let mut prev = data.len() as u8;
+        for (i, b) in data.iter().enumerate() {
+            codewords[i + 1] = prev << 4 | b >> 4;
+            prev = *b;
+        }
+        codewords[data.len() + 1] = prev << 4;
+        for (i, c) in codewords[data.len() + 2..data_len].iter_mut().enumerate() {
+            *c = if i % 2 == 0 { 0xec } else { 0x11 };
+        }
+        let (data_part, ec_part) = codewords.split_at_mut(data_len);
+        reed_solomon(data_part, &mut ec_part[..ec_len]);
+
+        let mut code = Grid {
+            size: 17 + 4 * version,
+            modules: [0; MAX_SIZE],
+            function: [0; MAX_SIZE],
+        };
+        code.draw_function_patterns(version);
+        code.draw_codewords(&codewords[..data_len + ec_len]);
+
+        let mut best_mask = 0;
+        let mut best_penalty = usize::MAX;
+        for mask in 0..8 {
+            code.apply_mask(mask);
+            code.draw_format_bits(mask);
+            let penalty = code.penalty();
+            if penalty < best_penalty {
+                best_mask = mask;
+                best_penalty = penalty;
+            }
+            // Masking twice undoes it
+            code.apply_mask(mask);
+        }
+        code.apply_mask(best_mask);
+        code.draw_format_bits(best_mask);
+        trace!("QR code version {} with mask {}", version, best_mask);
+
+        Some(QrCode {
+            size: code.size,
+            modules: code.modules,
+        })
+    }
+
+    /// Width and height in modules, without the quiet zone.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the module in column `x` and row `y` is dark.
+    pub fn module(&self, x: usize, y: usize) -> bool {
+        x < self.size && y < self.size && self.modules[y] >> x & 1 != 0
+    }
+
+    /// Like `module`, in coordinates that include the quiet zone.
+    fn module_with_quiet_zone(&self, x: usize, y: usize) -> bool {
+        x >= QUIET_ZONE && y >= QUIET_ZONE && self.module(x - QUIET_ZONE, y - QUIET_ZONE)
+    }
+}
+
+/// Draw `code` with its quiet zone, with the top left corner at `x`, `y` and each module
+/// `scale` pixels wide. The light modules are the lit pixels, so the code reads as dark on light.
+pub fn display_qr(code: &QrCode, x: i32, y: i32, scale: usize) {
+    let side = code.size() + 2 * QUIET_ZONE;
+    for row in 0..side {
+        let mut col = 0;
+        while col < side {
+            if code.module_with_quiet_zone(col, row) {
+                col += 1;
+                continue;
+            }
+            // Draw each run of light modules at once
+            let start = col;
+            while col < side && !code.module_with_quiet_zone(col, row) {
+                col += 1;
+            }
+            RectFull::new()
+                .pos(x + (start * scale) as i32, y + (row * scale) as i32)
+                .width(((col - start) * scale) as u32)
+                .height(scale as u32)
+                .display();
+        }
+    }
+}
+
+/// Show `text` as a QR code, as large as fits in the middle of the screen. The right button
+/// continues and both buttons reject. Returns `None` without showing anything if `text` is too
+/// long to encode.
+#[inline(never)]
+pub fn show_qr(text: &str) -> Option<()> {
+    let code = match QrCode::encode(text.as_bytes()) {
+        Some(code) => code,
+        None => {
+            trace!("Too long for a QR code: {}", text.len());
+            return None;
+        }
+    };
+    let side = code.size() + 2 * QUIET_ZONE;
+    let scale = core::cmp::max(1, SCREEN_HEIGHT / side);
+    let mut buttons = ButtonsState::new();
+    clear_screen();
+    display_qr(
+        &code,
+        (SCREEN_WIDTH.saturating_sub(side * scale) / 2) as i32,
+        (SCREEN_HEIGHT.saturating_sub(side * scale) / 2) as i32,
+        scale,
+    );
+    RIGHT_ARROW.instant_display();
+    loop {
+        match get_event(&mut buttons) {
+            Some(ButtonEvent::RightButtonRelease) => break Some(()),
+            Some(ButtonEvent::BothButtonsRelease) => {
+                trace!("User rejected at QR code");
+                break None;
+            }
+            Some(_) | None => (),
+        }
+    }
+}
+
+/// A code being built, with the modules that belong to function patterns marked so that data
+/// and masking skip them.
+struct Grid {
+    size: usize,
+    modules: [u64; MAX_SIZE],
+    function: [u64; MAX_SIZE],
+}
+
+impl Grid {
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y] >> x & 1 != 0
+    }
+
+    fn is_function(&self, x: usize, y: usize) -> bool {
+        self.function[y] >> x & 1 != 0
+    }
+
+    fn set(&mut self, x: usize, y: usize, dark: bool) {
+        if dark {
+            self.modules[y] |= 1 << x;
+        } else {
+            self.modules[y] &= !(1 << x);
+        }
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.set(x, y, dark);
+        self.function[y] |= 1 << x;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        for i in 0..self.size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+        let far = self.size - 4;
+        for (cx, cy) in [(3, 3), (far, 3), (3, far)] {
+            // Finder pattern with its light separator
+            for dy in -4..=4isize {
+                for dx in -4..=4isize {
+                    let (x, y) = (cx as isize + dx, cy as isize + dy);
+                    if (0..self.size as isize).contains(&x) && (0..self.size as isize).contains(&y)
+                    {
+                        let dist = core::cmp::max(dx.abs(), dy.abs());
+                        self.set_function(x as usize, y as usize, dist != 2 && dist != 4);
+                    }
+                }
+            }
+        }
+        if version >= 2 {
+            // Up to version 6 the only alignment pattern not overlapping a finder is this one
+            let pos = 4 * version + 10;
+            for dy in -2..=2isize {
+                for dx in -2..=2isize {
+                    let dist = core::cmp::max(dx.abs(), dy.abs());
+                    self.set_function(
+                        (pos as isize + dx) as usize,
+                        (pos as isize + dy) as usize,
+                        dist != 1,
+                    );
+                }
+            }
+        }
+        // Reserve the format bits until the mask is known
+        self.draw_format_bits(0);
+    }
+
+    fn draw_format_bits(&mut self, mask: usize) {
+        // Level L is 0b01
+        let data = 1 << 3 | mask;
+        let mut rem = data;
+        for _ in 0..10 {
+            rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+        }
+        let bits = (data << 10 | rem) ^ 0x5412;
+        let bit = |i: usize| bits >> i & 1 != 0;
+
+        // Around the top left finder
+        for i in 0..6 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+        // Split between the other two finders
+        for i in 0..8 {
+            self.set_function(self.size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, self.size - 15 + i, bit(i));
+        }
+        // Always dark
+        self.set_function(8, self.size - 8, true);
+    }
+
+    /// Place `codewords` in the zigzag order of two-module columns, from the bottom right.
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let mut i = 0;
+        let mut right = self.size - 1;
+        while right >= 1 {
+            // Skip the vertical timing pattern
+            if right == 6 {
+                right = 5;
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vert in 0..self.size {
+                let y = if upward { self.size - 1 - vert } else { vert };
+                for x in [right, right - 1] {
+                    if !self.is_function(x, y) && i < codewords.len() * 8 {
+                        self.set(x, y, codewords[i / 8] >> (7 - i % 8) & 1 != 0);
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: usize) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if invert && !self.is_function(x, y) {
+                    let dark = self.get(x, y);
+                    self.set(x, y, !dark);
+                }
+            }
+        }
+    }
+
+    /// Penalty score of the current modules, following the four rules of the standard. Any mask
+    /// gives a valid code; a low score only makes it easier to read.
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let mut penalty = 0;
+        for transpose in [false, true] {
+            let get = |a: usize, b: usize| {
+                if transpose {
+                    self.get(b, a)
+                } else {
+                    self.get(a, b)
+                }
+            };
+            for line in 0..size {
+                // Runs of five or more modules of the same colour
+                let mut run = 1;
+                for i in 1..size {
+                    if get(i, line) == get(i - 1, line) {
+                        run += 1;
+                        if run == 5 {
+                            penalty += 3;
+                        } else if run > 5 {
+                            penalty += 1;
+                        }
+                    } else {
+                        run = 1;
+                    }
+                }
+                // Finder-like 1:1:3:1:1 patterns with four light modules on one side
+                const PATTERN: [bool; 11] = [
+                    true, false, true, true, true, false, true, false, false, false, false,
+                ];
+                for i in 0..=size.saturating_sub(PATTERN.len()) {
+                    let forward = (0..PATTERN.len()).all(|j| get(i + j, line) == PATTERN[j]);
+                    let backward = (0..PATTERN.len())
+                        .all(|j| get(i + j, line) == PATTERN[PATTERN.len() - 1 - j]);
+                    if forward || backward {
+                        penalty += 40;
+                    }
+                }
+            }
+        }
+        // 2x2 blocks of the same colour
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let dark = self.get(x, y);
+                if dark == self.get(x + 1, y)
+                    && dark == self.get(x, y + 1)
+                    && dark == self.get(x + 1, y + 1)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+        // Balance of dark and light modules, in steps of 5% away from half
+        let dark: usize = self.modules[..size]
+            .iter()
+            .map(|row| row.count_ones() as usize)
+            .sum();
+        let total = size * size;
+        let percent = dark * 100 / total;
+        penalty + 10 * (percent.abs_diff(50) / 5)
+    }
+}
+
+/// Compute the error correction codewords for `data` into `ec`.
+fn reed_solomon(data: &[u8], ec: &mut [u8]) {
+    let degree = ec.len();
+    // Coefficients of the generator polynomial, highest first, without the leading 1
+    let mut generator = [0u8; 32];
+    generator[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            generator[j] = gf_mul(generator[j], root);
+            if j + 1 < degree {
+                generator[j] ^= generator[j + 1];
+            }
+        }
+        root = gf_mul(root, 0x02);
+    }
+
+    ec.fill(0);
+    for b in data {
+        let factor = b ^ ec[0];
+        ec.copy_within(1.., 0);
+        ec[degree - 1] = 0;
+        for (c, g) in ec.iter_mut().zip(generator.iter()) {
+            *c ^= gf_mul(*g, factor);
+        }
+    }
+}
+
+/// Multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1.
+fn gf_mul(x: u8, y: u8) -> u8 {
+    let mut z: u32 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11d);
+        z ^= ((y as u32 >> i) & 1) * x as u32;
+    }
+    z as u8
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec::Vec;
+
+    /// Format bits of level L for each mask, as listed in the standard.
+    const FORMAT_BITS: [usize; 8] = [
+        0b111011111000100,
+        0b111001011110011,
+        0b111110110101010,
+        0b111100010011101,
+        0b110011000101111,
+        0b110001100011000,
+        0b110110001000001,
+        0b110100101110110,
+    ];
+
+    /// The format bits of `code`, as read from around the top left finder and from between
+    /// the other two.
+    fn format_bits(code: &QrCode) -> (usize, usize) {
+        let size = code.size();
+        let mut top_left = [
+            (8, 0),
+            (8, 1),
+            (8, 2),
+            (8, 3),
+            (8, 4),
+            (8, 5),
+            (8, 7),
+            (8, 8),
+        ]
+        .to_vec();
+        top_left.extend([(7, 8), (5, 8), (4, 8), (3, 8), (2, 8), (1, 8), (0, 8)]);
+        let mut split: Vec<_> = (0..8).map(|i| (size - 1 - i, 8)).collect();
+        split.extend((8..15).map(|i| (8, size - 15 + i)));
+        let read = |modules: &[(usize, usize)]| {
+            modules
+                .iter()
+                .enumerate()
+                .map(|(i, &(x, y))| (code.module(x, y) as usize) << i)
+                .sum()
+        };
+        (read(&top_left), read(&split))
+    }
+
+    /// The codewords of `code`, unmasked and read in the zigzag order from the bottom right.
+    fn codewords(code: &QrCode) -> Vec<u8> {
+        let size = code.size();
+        let version = (size - 17) / 4;
+        let mask = FORMAT_BITS
+            .iter()
+            .position(|bits| *bits == format_bits(code).0)
+            .unwrap();
+        let mut grid = Grid {
+            size,
+            modules: [0; MAX_SIZE],
+            function: [0; MAX_SIZE],
+        };
+        grid.draw_function_patterns(version);
+        grid.modules = code.modules;
+        grid.apply_mask(mask);
+        let mut bits = Vec::new();
+        let mut right = size - 1;
+        let mut upward = true;
+        loop {
+            for vert in 0..size {
+                let y = if upward { size - 1 - vert } else { vert };
+                for x in [right, right - 1] {
+                    if !grid.is_function(x, y) {
+                        bits.push(grid.get(x, y) as u8);
+                    }
+                }
+            }
+            upward = !upward;
+            match right {
+                1 => break,
+                // The column pair left of the vertical timing pattern starts at 5
+                8 => right = 5,
+                _ => right -= 2,
+            }
+        }
+        bits.chunks_exact(8)
+            .map(|byte| byte.iter().fold(0, |b, bit| b << 1 | bit))
+            .collect()
+    }
+
+    #[test]
+    fn reed_solomon_of_the_standard_example() {
+        // "HELLO WORLD" at level M in version 1, the worked example of the standard
+        let data = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ];
+        let mut ec = [0; 10];
+        reed_solomon(&data, &mut ec);
+        assert_eq!(ec, [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+    }
+
+    #[test]
+    fn smallest_version_that_fits() {
+        let size = |len: usize| QrCode::encode(&[b'a'; 107][..len]).map(|code| code.size());
+        assert_eq!(size(0), Some(21));
+        assert_eq!(size(17), Some(21));
+        assert_eq!(size(18), Some(25));
+        assert_eq!(size(106), Some(37));
+        assert_eq!(size(107), None);
+    }
+
+    #[test]
+    fn function_patterns() {
+        let code = QrCode::encode(b"tz1VSUr8wwNhLAzempoch5d6hLRiTh8Cjcjb").unwrap();
+        let size = code.size();
+        for (x, y) in [(0, 0), (size - 7, 0), (0, size - 7)] {
+            // Dark ring, light ring, dark centre, and the light separator around them
+            for (d, dark) in [(0, true), (1, false), (2, true), (3, true)] {
+                assert_eq!(code.module(x + d, y + d), dark);
+            }
+        }
+        assert!(!code.module(7, 7));
+        for i in 8..size - 8 {
+            assert_eq!(code.module(6, i), i % 2 == 0);
+            assert_eq!(code.module(i, 6), i % 2 == 0);
+        }
+        assert!(code.module(8, size - 8));
+    }
+
+    #[test]
+    fn format_bits_are_from_the_table() {
+        for data in [
+            &b""[..],
+            b"hello",
+            b"bitcoin:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+        ] {
+            let code = QrCode::encode(data).unwrap();
+            let (top_left, split) = format_bits(&code);
+            assert_eq!(top_left, split);
+            assert!(FORMAT_BITS.contains(&top_left));
+        }
+    }
+
+    #[test]
+    fn data_reads_back() {
+        for data in [
+            &b"hello"[..],
+            b"bitcoin:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+        ] {
+            let code = QrCode::encode(data).unwrap();
+            let codewords = codewords(&code);
+            let version = (code.size() - 17) / 4;
+            let data_len = DATA_CODEWORDS[version - 1];
+            assert_eq!(codewords.len(), data_len + EC_CODEWORDS[version - 1]);
+            // Byte mode, the length, then the bytes, each shifted by half a byte
+            assert_eq!(codewords[0] >> 4, 0b0100);
+            assert_eq!((codewords[0] << 4 | codewords[1] >> 4) as usize, data.len());
+            let bytes: Vec<u8> = (0..data.len())
+                .map(|i| codewords[i + 1] << 4 | codewords[i + 2] >> 4)
+                .collect();
+            assert_eq!(bytes, data);
+            // The terminator, then the padding bytes
+            assert_eq!(codewords[data.len() + 1] & 0x0f, 0);
+            for (i, c) in codewords[data.len() + 2..data_len].iter().enumerate() {
+                assert_eq!(*c, if i % 2 == 0 { 0xec } else { 0x11 });
+            }
+            let mut ec = [0; 26];
+            let ec = &mut ec[..codewords.len() - data_len];
+            reed_solomon(&codewords[..data_len], ec);
+            assert_eq!(ec, &codewords[data_len..]);
+        }
+    }
+}
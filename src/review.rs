@@ -0,0 +1,163 @@
+//! Reviews of several fields in turn, keeping track of the fields the user has confirmed so
+//! that a review can be left and entered again.
+
+use crate::strings::strings;
+use crate::*;
+use core::fmt::Write;
+
+/// One field of a `Review`: its title and a closure writing its value.
+pub struct Field<'a> {
+    pub title: &'a str,
+    pub contents: SectionContents<'a, 16>,
+}
+
+/// Number of fields of a `Review` whose confirmation is tracked. Fields after these are asked
+/// for every time.
+pub const MAX_TRACKED_FIELDS: usize = 64;
+
+const OVERVIEW_TEXT_X: usize = 22;
+const OVERVIEW_CHECK: Icon = Icon::from(&bitmaps::CHECK_GLYPH).set_x(10);
+
+pub struct Review<'a> {
+    fields: &'a [Field<'a>],
+    confirmed: u64,
+    overview: bool,
+    max_field_pages: Option<usize>,
+}
+
+impl<'a> Review<'a> {
+    pub fn new(fields: &'a [Field<'a>]) -> Self {
+        Review {
+            fields,
+            confirmed: 0,
+            overview: false,
+            max_field_pages: None,
+        }
+    }
+
+    /// When `ask` is called again after some fields were confirmed, first show the overview of
+    /// the fields with `show_overview`.
+    pub fn with_overview(self, overview: bool) -> Self {
+        Review { overview, ..self }
+    }
+
+    /// Show at most `max_field_pages` pages of each field. A field that does not fit is cut
+    /// short with a marker on its last page, and the user has to acknowledge that it was cut
+    /// before it counts as confirmed.
+    pub fn with_max_field_pages(self, max_field_pages: usize) -> Self {
+        Review {
+            max_field_pages: Some(max_field_pages),
+            ..self
+        }
+    }
+
+    pub fn is_confirmed(&self, field: usize) -> bool {
+        field < MAX_TRACKED_FIELDS && self.confirmed & (1 << field) != 0
+    }
+
+    pub fn confirmed_count(&self) -> usize {
+        (0..self.fields.len())
+            .filter(|field| self.is_confirmed(*field))
+            .count()
+    }
+
+    /// Page through `field`, marking it as confirmed if the user accepts it. There is nothing
+    /// to accept, so `None` is returned, if there is no such field.
+    pub fn ask_field(&mut self, field: usize) -> Option<()> {
+        let Field { title, contents } = *self.fields.get(field)?;
+        let mut scroller = WriteScroller::<_, 16>::new(title, |w| contents(w));
+        if let Some(max_pages) = self.max_field_pages {
+            scroller = scroller.with_max_pages(max_pages);
+        }
+        let truncated = scroller.is_truncated().ok()?;
+        if scroller.with_show_index(true).run() != PromptOutcome::Accepted {
+            trace!("User rejected field");
+            return None;
+        }
+        if truncated {
+            warning_prompt(strings().field_truncated)?;
+        }
+        if field < MAX_TRACKED_FIELDS {
+            self.confirmed |= 1 << field;
+        }
+        Some(())
+    }
+
+    /// Ask for each field that is not confirmed yet, in order, then for the final approval.
+    pub fn ask(&mut self) -> Option<()> {
+        if self.overview && self.confirmed_count() > 0 {
+            self.show_overview()?;
+        }
+        for field in 0..self.fields.len() {
+            if !self.is_confirmed(field) {
+                self.ask_field(field)?;
+            }
+        }
+        final_accept_prompt(&[strings().approve])
+    }
+
+    /// Show the titles of all fields, three to a page, with a check by the confirmed ones.
+    pub fn show_overview(&self) -> Option<()> {
+        let rows = THREE_ROWS_Y.len();
+        let page_count = crate::page_count(self.fields.len(), 1, rows);
+        let draw = |page: usize| -> Result<(), ScrollerError> {
+            clear_screen();
+            strings()
+                .review
+                .place(Location::Top, Layout::Centered, false);
+            for (row, y) in THREE_ROWS_Y.iter().enumerate() {
+                let field = page * rows + row;
+                if field >= self.fields.len() {
+                    break;
+                }
+                if self.is_confirmed(field) {
+                    OVERVIEW_CHECK.set_y(*y as i16 + 3).display();
+                }
+                self.fields[field].title.place(
+                    Location::Custom(*y),
+                    Layout::Custom(OVERVIEW_TEXT_X),
+                    false,
+                );
+            }
+            if page > 0 {
+                LEFT_ARROW.instant_display();
+            }
+            if page + 1 < page_count {
+                RIGHT_ARROW.instant_display();
+            } else {
+                RIGHT_CHECK.instant_display();
+            }
+            Ok(())
+        };
+        match page_loop(page_count, draw) {
+            Ok(true) => Some(()),
+            _ => None,
+        }
+    }
+}
+
+/// Ask to accept or reject `action` after a review, e.g. "Sign transaction", under lines
+/// telling how many fields were reviewed.
+pub fn review_summary(field_count: usize, action: &str) -> Decision {
+    let strings = strings();
+    // Room for any count and a translated label
+    let mut count: ArrayString<32> = ArrayString::new();
+    let _ = write!(
+        mk_prompt_write(&mut count),
+        "{} {}",
+        field_count,
+        if field_count == 1 {
+            strings.item
+        } else {
+            strings.items
+        }
+    );
+    Decision::from(
+        MessageValidator::new(
+            &[strings.reviewed, count.as_str(), action],
+            &[strings.approve],
+            &[strings.reject],
+        )
+        .ask(),
+    )
+}
@@ -0,0 +1,211 @@
+//! An off-screen framebuffer that pages can be rendered into, so that tests can compare them
+//! against stored snapshots without a device.
+//!
+//! There is no device font off-device, so text is drawn as one box per character, as wide as
+//! the character's advance in the given `FontMetrics`. Glyphs are drawn pixel for pixel.
+
+use crate::*;
+use ledger_device_sdk::ui::bitmaps::Glyph;
+
+/// A monochrome screen, one bit per pixel with the leftmost pixel of a row in the lowest bit.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Framebuffer {
+    rows: [u128; SCREEN_HEIGHT],
+}
+
+impl Framebuffer {
+    pub const fn new() -> Self {
+        Framebuffer {
+            rows: [0; SCREEN_HEIGHT],
+        }
+    }
+
+    pub fn rows(&self) -> &[u128; SCREEN_HEIGHT] {
+        &self.rows
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < SCREEN_WIDTH && y < SCREEN_HEIGHT && self.rows[y] >> x & 1 != 0
+    }
+
+    pub fn set(&mut self, x: usize, y: usize) {
+        if x < SCREEN_WIDTH && y < SCREEN_HEIGHT {
+            self.rows[y] |= 1 << x;
+        }
+    }
+
+    /// Light the pixels of a rectangle, clipped to the screen.
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        for y in y..y + height {
+            for x in x..x + width {
+                self.set(x, y);
+            }
+        }
+    }
+
+    /// Draw `glyph` with its top left corner at `x`, `y`. The bitmap holds one bit per pixel,
+    /// row after row, lowest bit first, as made by `include_gif!`.
+    pub fn draw_glyph(&mut self, glyph: &Glyph, x: usize, y: usize) {
+        let (width, height) = (glyph.width as usize, glyph.height as usize);
+        for row in 0..height {
+            for col in 0..width {
+                let bit = row * width + col;
+                let lit =
+                    matches!(glyph.bitmap.get(bit / 8), Some(byte) if byte >> (bit % 8) & 1 != 0);
+                if lit != glyph.inverted {
+                    self.set(x + col, y + row);
+                }
+            }
+        }
+    }
+
+    /// Draw `text` on the row starting at `y`, placed like `StringPlace::place` would with
+    /// `layout`, with its characters sized by `metrics`.
+    pub fn draw_text(
+        &mut self,
+        text: &str,
+        y: usize,
+        layout: Layout,
+        bold: bool,
+        metrics: &impl FontMetrics,
+    ) {
+        let width = metrics.text_width(text, bold);
+        let mut x = match layout {
+            Layout::LeftAligned => 0,
+            Layout::RightAligned => SCREEN_WIDTH.saturating_sub(width),
+            Layout::Centered => SCREEN_WIDTH.saturating_sub(width) / 2,
+            Layout::Custom(x) => x,
+        };
+        for c in text.chars() {
+            let advance = metrics.advance(c, bold);
+            if !c.is_whitespace() {
+                self.fill_rect(x, y + 1, advance.saturating_sub(1), TEXT_ROW_HEIGHT - 2);
+            }
+            x += advance;
+        }
+    }
+
+    /// Draw what the test harness has on the screen: its text, with the harness's char widths,
+    /// and its rectangles. As for `WriteScroller::render_page`, icons are left out, since their
+    /// bitmaps come from the SDK.
+    #[cfg(feature = "test-harness")]
+    pub fn draw_harness_screen(&mut self) {
+        for row in harness::screen() {
+            let layout = Layout::Custom(row.x);
+            self.draw_text(&row.text, row.y, layout, row.bold, &DeviceFont);
+        }
+        for rect in harness::rects() {
+            let (x, y) = (rect.x.max(0) as usize, rect.y.max(0) as usize);
+            self.fill_rect(x, y, rect.width as usize, rect.height as usize);
+        }
+    }
+
+    /// Write the screen as lines of `#` for lit and `.` for dark pixels, for snapshots that
+    /// can be read and diffed.
+    pub fn write_ascii<W: Write>(&self, w: &mut W) -> core::fmt::Result {
+        for y in 0..SCREEN_HEIGHT {
+            for x in 0..SCREEN_WIDTH {
+                w.write_char(if self.get(x, y) { '#' } else { '.' })?;
+            }
+            w.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Framebuffer {
+    fn default() -> Self {
+        Framebuffer::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::String;
+
+    /// Check that `framebuffer` is drawn as `snapshot`, as written by `write_ascii`.
+    fn assert_snapshot(framebuffer: &Framebuffer, snapshot: &str) {
+        let mut drawn = String::new();
+        framebuffer.write_ascii(&mut drawn).unwrap();
+        assert!(
+            drawn == snapshot,
+            "The screen differs from its snapshot:\n{}",
+            drawn
+        );
+    }
+
+    fn memo() -> WriteScroller<'static, impl PromptSource<16>, 16> {
+        WriteScroller::<_, 16>::new("Memo", |w| {
+            Ok(w.write_str("Lunch at noon, as agreed last week")?)
+        })
+    }
+
+    #[test]
+    fn single_row_page_is_drawn_as_its_snapshot() {
+        let mut framebuffer = Framebuffer::new();
+        let metrics = MonospaceFont { cell_width: 6 };
+        memo()
+            .render_page(true, 1, &mut framebuffer, &metrics)
+            .unwrap();
+        assert_snapshot(&framebuffer, include_str!("snapshots/single_row.txt"));
+    }
+
+    #[cfg(feature = "test-harness")]
+    #[test]
+    fn rendered_page_is_what_is_shown() {
+        let scroller = memo().with_show_index(true);
+        let _ = harness::play([], || scroller.run());
+        let mut shown = Framebuffer::new();
+        shown.draw_harness_screen();
+        let mut rendered = Framebuffer::new();
+        scroller
+            .render_page(true, 0, &mut rendered, &DeviceFont)
+            .unwrap();
+        assert_eq!(shown, rendered);
+    }
+
+    #[cfg(feature = "test-harness")]
+    #[test]
+    fn three_row_page_is_drawn_as_its_snapshot() {
+        let scroller = WriteScroller::<_, 16>::new("Log", |w| {
+            Ok(w.write_str("Boot\nConnected to host\nReady")?)
+        })
+        .with_three_rows(true);
+        let _ = harness::play([], || scroller.run());
+        let mut framebuffer = Framebuffer::new();
+        framebuffer.draw_harness_screen();
+        assert_snapshot(&framebuffer, include_str!("snapshots/three_rows.txt"));
+    }
+
+    #[cfg(feature = "test-harness")]
+    #[test]
+    fn sections_page_is_drawn_as_its_snapshot() {
+        let sections = [
+            Section {
+                header: "Recipient",
+                contents: &|w| Ok(w.write_str("Alice")?),
+            },
+            Section {
+                header: "Amount",
+                contents: &|w| Ok(w.write_str("5 ATOM")?),
+            },
+        ];
+        let _ = harness::play([], || write_sections(true, "Transfer", &sections, false));
+        let mut framebuffer = Framebuffer::new();
+        framebuffer.draw_harness_screen();
+        assert_snapshot(&framebuffer, include_str!("snapshots/sections.txt"));
+    }
+
+    #[cfg(feature = "test-harness")]
+    #[test]
+    fn menu_is_drawn_as_its_snapshot() {
+        let options = [("Mainnet", 1), ("Testnet", 2)];
+        let _ = harness::play([], || select_from("Network", &options));
+        let mut framebuffer = Framebuffer::new();
+        framebuffer.draw_harness_screen();
+        assert_snapshot(&framebuffer, include_str!("snapshots/menu.txt"));
+    }
+}
@@ -0,0 +1,111 @@
+//! Labels shown by the built-in prompts, so that apps can translate them.
+
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+pub struct Strings {
+    pub approve: &'static str,
+    pub reject: &'static str,
+    pub accept: &'static str,
+    pub fee: &'static str,
+    pub high_fee: &'static str,
+    pub total: &'static str,
+    pub running_total: &'static str,
+    pub unexpected_nonce: &'static str,
+    pub expected: &'static str,
+    pub value_match_question: &'static str,
+    pub value_match_confirm: &'static str,
+    pub value_match_reject: &'static str,
+    pub display_failed: &'static str,
+    pub invalid_utf8: &'static str,
+    pub too_many_pages: &'static str,
+    pub too_much_content: &'static str,
+    pub method: &'static str,
+    pub confirm_call: &'static str,
+    pub not_text: &'static str,
+    pub account: &'static str,
+    pub from: &'static str,
+    pub sign_request: &'static str,
+    pub grant_scope: &'static str,
+    pub grant_access: &'static str,
+    pub review: &'static str,
+    pub unlocks: &'static str,
+    pub amount: &'static str,
+    pub vesting: &'static str,
+    pub address: &'static str,
+    pub to: &'static str,
+    pub same_address: &'static str,
+    pub confirm_transfer: &'static str,
+    pub field_truncated: &'static str,
+    pub reviewed: &'static str,
+    pub item: &'static str,
+    pub items: &'static str,
+    pub blind_signing: &'static str,
+    pub proceed_with_caution: &'static str,
+    pub yes: &'static str,
+    pub no: &'static str,
+    pub cancel: &'static str,
+}
+
+impl Strings {
+    pub const ENGLISH: Strings = Strings {
+        approve: "Approve",
+        reject: "Reject",
+        accept: "Accept",
+        fee: "Fee",
+        high_fee: "High fee",
+        total: "Total",
+        running_total: "Running total",
+        unexpected_nonce: "Unexpected nonce",
+        expected: "expected",
+        value_match_question: "Does it match?",
+        value_match_confirm: "Yes, it matches",
+        value_match_reject: "No, reject",
+        display_failed: "Display failed",
+        invalid_utf8: "Invalid UTF-8",
+        too_many_pages: "Too many pages",
+        too_much_content: "Too much content",
+        method: "Method",
+        confirm_call: "Confirm call",
+        not_text: "Not UTF-8 text",
+        account: "Account",
+        from: "From",
+        sign_request: "Sign request",
+        grant_scope: "Grant scope?",
+        grant_access: "Grant access",
+        review: "Review",
+        unlocks: "Unlocks",
+        amount: "Amount",
+        vesting: "Vesting",
+        address: "Address",
+        to: "To",
+        same_address: "Sending to self",
+        confirm_transfer: "Confirm transfer",
+        field_truncated: "Field truncated",
+        reviewed: "Reviewed",
+        item: "item",
+        items: "items",
+        blind_signing: "Blind signing",
+        proceed_with_caution: "Proceed with caution",
+        yes: "Yes",
+        no: "No",
+        cancel: "Cancel",
+    };
+}
+
+static STRINGS: AtomicPtr<Strings> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Use `strings` for all labels of the built-in prompts from now on. Call this once at startup.
+pub fn set_strings(strings: &'static Strings) {
+    STRINGS.store(strings as *const Strings as *mut Strings, Ordering::Relaxed);
+}
+
+/// The labels set with `set_strings`, or `Strings::ENGLISH` if there are none.
+pub fn strings() -> &'static Strings {
+    let strings = STRINGS.load(Ordering::Relaxed);
+    if strings.is_null() {
+        &Strings::ENGLISH
+    } else {
+        // Only ever set from a &'static Strings in set_strings
+        unsafe { &*strings }
+    }
+}